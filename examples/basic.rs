@@ -6,7 +6,7 @@ fn main() -> Result<(), FileRegionError> {
     let mut file = tempfile()?;
     file.write_all(b"Hello, FileRegion.")?;
 
-    let mut region = FileRegion::new(&file, 7..16);
+    let region = FileRegion::new(&file, 7..16);
     let mut buffer = [0; 9];
     region.read(0, &mut buffer)?;
     assert_eq!(&buffer, b"FileRegio");
@@ -0,0 +1,60 @@
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+/// An access-pattern hint for `FileRegion::advise()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Advice {
+    /// No particular pattern; undoes earlier advice.
+    Normal,
+    /// The region will be read from start to end.
+    Sequential,
+    /// The region will be accessed in random order.
+    Random,
+    /// The region will be needed soon, so the kernel may start reading it.
+    WillNeed,
+    /// The region will not be needed soon, so its cached pages may be dropped.
+    DontNeed,
+    /// The region will be accessed only once.
+    NoReuse,
+}
+
+impl FileRegion<'_> {
+    /// Tells the kernel how the region's byte range will be accessed, using
+    /// `posix_fadvise` where available. Advice is only a hint: on platforms
+    /// without `posix_fadvise` this does nothing and returns `Ok(())`.
+    ///
+    /// May return an I/O error from `posix_fadvise`.
+    pub fn advise(&self, advice: Advice) -> Result<(), FileRegionError> {
+        if self.is_empty() {
+            // A zero length means "to the end of the file" to posix_fadvise.
+            return Ok(());
+        }
+        fadvise(self, advice).map_err(FileRegionError::Io)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+fn fadvise(region: &FileRegion, advice: Advice) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let advice = match advice {
+        Advice::Normal => libc::POSIX_FADV_NORMAL,
+        Advice::Sequential => libc::POSIX_FADV_SEQUENTIAL,
+        Advice::Random => libc::POSIX_FADV_RANDOM,
+        Advice::WillNeed => libc::POSIX_FADV_WILLNEED,
+        Advice::DontNeed => libc::POSIX_FADV_DONTNEED,
+        Advice::NoReuse => libc::POSIX_FADV_NOREUSE,
+    };
+    let offset = libc::off_t::try_from(region.range.start).unwrap_or(libc::off_t::MAX);
+    let len = libc::off_t::try_from(region.len()).unwrap_or(libc::off_t::MAX);
+    // SAFETY: the descriptor is borrowed from a live `File`.
+    match unsafe { libc::posix_fadvise(region.file.as_raw_fd(), offset, len, advice) } {
+        0 => Ok(()),
+        errno => Err(std::io::Error::from_raw_os_error(errno)),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+fn fadvise(_region: &FileRegion, _advice: Advice) -> std::io::Result<()> {
+    Ok(())
+}
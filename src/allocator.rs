@@ -0,0 +1,67 @@
+use std::fs::File;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::error::{FileRegionError, RegionError};
+use super::owned::OwnedFileRegion;
+
+/// Hands out disjoint regions of a pre-sized file, for parallel writers such
+/// as multi-connection downloaders. Regions are allocated back to back from
+/// the start of the file, so no two allocations overlap, and each comes with
+/// its own handle to the file so it can be moved to another thread.
+///
+/// The handles share the file's cursor, which is harmless: reads and writes
+/// through the allocated regions use positional I/O.
+pub struct RegionAllocator {
+    file: File,
+    capacity: u64,
+    next: AtomicU64,
+}
+
+impl RegionAllocator {
+    /// Creates an allocator over `file`, first setting its length to
+    /// `capacity`.
+    ///
+    /// May return an I/O error from resizing the file.
+    pub fn new(file: File, capacity: u64) -> Result<RegionAllocator, FileRegionError> {
+        file.set_len(capacity)?;
+        Ok(RegionAllocator {
+            file,
+            capacity,
+            next: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the file.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the size of the file being divided up.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Returns the number of bytes not yet allocated.
+    pub fn remaining(&self) -> u64 {
+        self.capacity - self.next.load(Ordering::Acquire)
+    }
+
+    /// Allocates the next `len` bytes. Safe to call from several threads at
+    /// once.
+    ///
+    /// Returns `RegionError::EndOutOfBounds` if fewer than `len` bytes remain,
+    /// or an I/O error from duplicating the file handle.
+    pub fn alloc(&self, len: u64) -> Result<OwnedFileRegion, FileRegionError> {
+        let start = self
+            .next
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |next| {
+                next.checked_add(len).filter(|&end| end <= self.capacity)
+            })
+            .map_err(|next| RegionError::EndOutOfBounds {
+                end: next.saturating_add(len),
+                limit: self.capacity,
+            })?;
+        let file = self.file.try_clone()?;
+        Ok(OwnedFileRegion::new(file, start..start + len))
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs::File;
+
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+use super::positional::write_all_at;
+
+/// A region that grows as it is written, up to a fixed capacity. Useful for
+/// fixed-capacity slots in a log file: the slot is `start..start + capacity`,
+/// and the region covers only the part written so far.
+///
+/// Writes that extend past the end of the file grow the file.
+pub struct AppendRegion<'a> {
+    file: &'a File,
+    start: u64,
+    len: u64,
+    capacity: u64,
+}
+
+impl<'a> AppendRegion<'a> {
+    /// Creates a new, empty `AppendRegion` starting at `start` that can grow
+    /// to `capacity` bytes. Performs no I/O.
+    pub fn new(file: &'a File, start: u64, capacity: u64) -> AppendRegion<'a> {
+        AppendRegion {
+            file,
+            start,
+            len: 0,
+            capacity,
+        }
+    }
+
+    /// Creates an `AppendRegion` whose first `len` bytes have already been
+    /// written, e.g. to resume a slot. Returns `RegionError::EndOutOfBounds` if
+    /// `len` exceeds `capacity`.
+    pub fn with_len(
+        file: &'a File,
+        start: u64,
+        len: u64,
+        capacity: u64,
+    ) -> Result<AppendRegion<'a>, RegionError> {
+        if len > capacity {
+            return Err(RegionError::EndOutOfBounds {
+                end: len,
+                limit: capacity,
+            });
+        }
+        Ok(AppendRegion {
+            file,
+            start,
+            len,
+            capacity,
+        })
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns if nothing has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum length of the region.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Returns how many more bytes the region can grow by.
+    pub fn remaining(&self) -> u64 {
+        self.capacity - self.len
+    }
+
+    /// Returns a `FileRegion` over the bytes written so far.
+    pub fn region(&self) -> FileRegion<'a> {
+        FileRegion::new(self.file, self.start..self.start + self.len)
+    }
+
+    /// Writes all of `buf` at `offset`, growing the region if the write ends
+    /// past its current end. `offset` may equal `len()` but not exceed it, so
+    /// the region never has gaps.
+    ///
+    /// Returns `RegionError::StartOutOfBounds` if `offset` is past the end, or
+    /// `RegionError::EndOutOfBounds` if the write would exceed the capacity; in
+    /// both cases nothing is written.
+    pub fn write(&mut self, offset: u64, buf: &[u8]) -> Result<(), FileRegionError> {
+        if offset > self.len {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len,
+            }));
+        }
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .filter(|&end| end <= self.capacity)
+            .ok_or(RegionError::EndOutOfBounds {
+                end: offset.saturating_add(buf.len() as u64),
+                limit: self.capacity,
+            })?;
+        let at = self
+            .start
+            .checked_add(offset)
+            .ok_or(RegionError::StartOverflow)?;
+        write_all_at(self.file, buf, at)?;
+        self.len = self.len.max(end);
+        Ok(())
+    }
+
+    /// Writes all of `buf` at the current end, growing the region. Returns the
+    /// offset, relative to the region, at which `buf` was written.
+    ///
+    /// Has the same errors as `write()`.
+    pub fn append(&mut self, buf: &[u8]) -> Result<u64, FileRegionError> {
+        let offset = self.len;
+        self.write(offset, buf)?;
+        Ok(offset)
+    }
+}
@@ -0,0 +1,107 @@
+//! Async region I/O on `tokio-uring`'s owned-buffer model (Linux only,
+//! `tokio-uring` feature).
+//!
+//! `tokio-uring` takes ownership of each buffer for the duration of an
+//! operation and hands it back with the result. `UringRegion` keeps that
+//! shape while bounding every submission to the region, so callers no longer
+//! have to repeat the bounds checks around each `read_at`/`write_at`.
+
+use std::ops::Range;
+
+use tokio_uring::buf::{BoundedBuf, IoBuf, IoBufMut};
+use tokio_uring::fs::File;
+
+use super::core::{clamped_subrange, subrange};
+use super::error::{FileRegionError, RegionError};
+
+/// A contiguous byte range of a `tokio_uring::fs::File`. Offsets are
+/// relative to the start of the region, with the same bounds semantics as
+/// `FileRegion`.
+pub struct UringRegion<'f> {
+    file: &'f File,
+    range: Range<u64>,
+}
+
+impl<'f> UringRegion<'f> {
+    /// Creates a new `UringRegion`. The `range` is _not_ validated against the
+    /// file.
+    pub fn new(file: &'f File, range: Range<u64>) -> UringRegion<'f> {
+        UringRegion { file, range }
+    }
+
+    /// Returns the file.
+    pub fn file(&self) -> &'f File {
+        self.file
+    }
+
+    /// Returns the region (a range).
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns the length of the region.
+    pub fn len(&self) -> u64 {
+        self.range.end.saturating_sub(self.range.start)
+    }
+
+    /// Returns true if the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Reads into `buf` from `offset`, reading no further than the end of the
+    /// region, and returns the buffer with the result. As with
+    /// `FileRegion::read()`, returns `RegionError::StartOutOfBounds` if
+    /// `offset` is at or past the end of the region.
+    pub async fn read_at<T: IoBufMut>(
+        &self,
+        offset: u64,
+        buf: T,
+    ) -> (Result<usize, FileRegionError>, T) {
+        let range = match clamped_subrange(&self.range, offset, buf.bytes_total()) {
+            Ok(range) => range,
+            Err(error) => return (Err(error.into()), buf),
+        };
+        let len = (range.end - range.start) as usize;
+        let (result, slice) = self.file.read_at(buf.slice(..len), range.start).await;
+        (result.map_err(FileRegionError::Io), slice.into_inner())
+    }
+
+    /// Writes from `buf` at `offset` in a single submission and returns the
+    /// buffer with the number of bytes written. As with `FileRegion::write()`,
+    /// writes nothing and returns a region error unless all of `buf` fits in
+    /// the region.
+    pub async fn write_at<T: IoBuf>(
+        &self,
+        offset: u64,
+        buf: T,
+    ) -> (Result<usize, FileRegionError>, T) {
+        let start = match self.bounds(offset, &buf) {
+            Ok(start) => start,
+            Err(error) => return (Err(error), buf),
+        };
+        let (result, buf) = self.file.write_at(buf, start).submit().await;
+        (result.map_err(FileRegionError::Io), buf)
+    }
+
+    /// Like `write_at()`, but resubmits until all of `buf` has been written.
+    pub async fn write_all_at<T: IoBuf>(
+        &self,
+        offset: u64,
+        buf: T,
+    ) -> (Result<(), FileRegionError>, T) {
+        let start = match self.bounds(offset, &buf) {
+            Ok(start) => start,
+            Err(error) => return (Err(error), buf),
+        };
+        let (result, buf) = self.file.write_all_at(buf, start).await;
+        (result.map_err(FileRegionError::Io), buf)
+    }
+
+    fn bounds(&self, offset: u64, buf: &impl IoBuf) -> Result<u64, FileRegionError> {
+        let end = offset
+            .checked_add(buf.bytes_init() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        Ok(subrange(&self.range, offset..end)?.start)
+    }
+}
@@ -0,0 +1,232 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Result as IoResult, Seek, SeekFrom, Write};
+use std::sync::{Mutex, PoisonError};
+
+use super::positional;
+
+/// Positional storage that a `FileRegion` can be built on. Offsets are
+/// absolute within the backend.
+///
+/// Implemented for `File`, using positional I/O, and for byte slices and
+/// `Vec<u8>` as read-only in-memory backends. `SeekBackend` adapts sources
+/// that can only seek. Backends that can also be written implement
+/// `WriteBackend`.
+pub trait RegionBackend {
+    /// Reads into `buf` at `offset`, returning the number of bytes read; 0 at
+    /// or past the end of the backend.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize>;
+
+    /// Returns the current length of the backend in bytes.
+    fn len(&self) -> IoResult<u64>;
+
+    /// Returns if the backend is currently empty.
+    fn is_empty(&self) -> IoResult<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+/// A `RegionBackend` that can also be written. Writes take `&self`, as they
+/// do for a `File`, so several regions over one backend can write at once.
+///
+/// Implemented for `File`, and for `RefCell<Vec<u8>>` and `Mutex<Vec<u8>>` as
+/// writable in-memory backends for deterministic tests.
+pub trait WriteBackend: RegionBackend {
+    /// Writes from `buf` at `offset`, returning the number of bytes written.
+    /// Writing past the end grows the backend.
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize>;
+}
+
+impl RegionBackend for File {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        positional::read_at(self, buf, offset)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(self.metadata()?.len())
+    }
+}
+
+impl WriteBackend for File {
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        positional::write_at(self, buf, offset)
+    }
+}
+
+impl RegionBackend for [u8] {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        let Some(src) = usize::try_from(offset).ok().and_then(|at| self.get(at..)) else {
+            return Ok(0);
+        };
+        let n = buf.len().min(src.len());
+        buf[..n].copy_from_slice(&src[..n]);
+        Ok(n)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(<[u8]>::len(self) as u64)
+    }
+}
+
+impl RegionBackend for Vec<u8> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        self.as_slice().read_at(offset, buf)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(Vec::len(self) as u64)
+    }
+}
+
+impl RegionBackend for RefCell<Vec<u8>> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        self.borrow().read_at(offset, buf)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(self.borrow().len() as u64)
+    }
+}
+
+/// Writing past the end zero-fills any gap, as writing past the end of a
+/// file does.
+impl WriteBackend for RefCell<Vec<u8>> {
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        write_vec_at(&mut self.borrow_mut(), offset, buf)
+    }
+}
+
+/// A poisoned lock is used anyway: the bytes are always in a usable state.
+impl RegionBackend for Mutex<Vec<u8>> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_at(offset, buf)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(self.lock().unwrap_or_else(PoisonError::into_inner).len() as u64)
+    }
+}
+
+/// Writing past the end zero-fills any gap, as writing past the end of a
+/// file does.
+impl WriteBackend for Mutex<Vec<u8>> {
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        let mut vec = self.lock().unwrap_or_else(PoisonError::into_inner);
+        write_vec_at(&mut vec, offset, buf)
+    }
+}
+
+fn write_vec_at(vec: &mut Vec<u8>, offset: u64, buf: &[u8]) -> IoResult<usize> {
+    let start = usize::try_from(offset).map_err(|_| ErrorKind::InvalidInput)?;
+    let end = start
+        .checked_add(buf.len())
+        .ok_or(ErrorKind::InvalidInput)?;
+    if vec.len() < end {
+        vec.resize(end, 0);
+    }
+    vec[start..end].copy_from_slice(buf);
+    Ok(buf.len())
+}
+
+/// Adapts any `Read + Seek` source (or `Read + Write + Seek` sink) without
+/// positional I/O, such as a `Cursor<Vec<u8>>` or a custom archive reader,
+/// for use as a `RegionBackend`.
+///
+/// Every operation locks the inner value and seeks it first, so regions over
+/// one `SeekBackend` can be shared between threads, but their I/O does not
+/// run concurrently.
+#[derive(Debug, Default)]
+pub struct SeekBackend<T>(Mutex<T>);
+
+impl<T> SeekBackend<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> SeekBackend<T> {
+        SeekBackend(Mutex::new(inner))
+    }
+
+    /// Returns a mutable reference to the inner value. Seeking it is
+    /// harmless, since every operation seeks first.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Consumes the backend, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Read + Seek> RegionBackend for SeekBackend<T> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        let mut inner = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.seek(SeekFrom::Start(offset))?;
+        inner.read(buf)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        let mut inner = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.seek(SeekFrom::End(0))
+    }
+}
+
+impl<T: Read + Write + Seek> WriteBackend for SeekBackend<T> {
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        let mut inner = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.seek(SeekFrom::Start(offset))?;
+        inner.write(buf)
+    }
+}
+
+/// Adapts any handle implementing the platform `FileExt` trait, such as a
+/// wrapper around an `O_DIRECT` handle or a device file, for use as a
+/// `RegionBackend`. Uses `read_at`/`write_at` on Unix and
+/// `seek_read`/`seek_write` on Windows.
+///
+/// `len()` duplicates the handle to read its metadata.
+#[cfg(any(unix, windows))]
+#[derive(Debug)]
+pub struct FileExtBackend<T>(pub T);
+
+#[cfg(unix)]
+impl<T: std::os::unix::fs::FileExt + std::os::fd::AsFd> RegionBackend for FileExtBackend<T> {
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        self.0.read_at(buf, offset)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        let file = File::from(self.0.as_fd().try_clone_to_owned()?);
+        Ok(file.metadata()?.len())
+    }
+}
+
+#[cfg(unix)]
+impl<T: std::os::unix::fs::FileExt + std::os::fd::AsFd> WriteBackend for FileExtBackend<T> {
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        self.0.write_at(buf, offset)
+    }
+}
+
+#[cfg(windows)]
+impl<T: std::os::windows::fs::FileExt + std::os::windows::io::AsHandle> RegionBackend
+    for FileExtBackend<T>
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        self.0.seek_read(buf, offset)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        let file = File::from(self.0.as_handle().try_clone_to_owned()?);
+        Ok(file.metadata()?.len())
+    }
+}
+
+#[cfg(windows)]
+impl<T: std::os::windows::fs::FileExt + std::os::windows::io::AsHandle> WriteBackend
+    for FileExtBackend<T>
+{
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        self.0.seek_write(buf, offset)
+    }
+}
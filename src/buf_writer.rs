@@ -0,0 +1,96 @@
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+/// Coalesces many small writes to a region into fewer, larger ones.
+///
+/// Consecutive writes, each starting where the last one ended, accumulate
+/// in a buffer of fixed capacity. The buffer is written out when it would
+/// overflow, when a write is not contiguous with it, on `flush()`, and on
+/// drop (where errors are ignored; call `flush()` to see them). Writes at
+/// least as large as the capacity bypass the buffer.
+///
+/// Every write is checked against the region's bounds when it is made, so
+/// an out-of-bounds write fails immediately rather than at the next flush.
+pub struct RegionBufWriter<'a> {
+    region: FileRegion<'a>,
+    buf: Vec<u8>,
+    start: u64,
+}
+
+impl<'a> RegionBufWriter<'a> {
+    /// Creates a new `RegionBufWriter` with a buffer of the region's
+    /// `buffer_size()`, 8 KiB by default.
+    pub fn new(region: FileRegion<'a>) -> RegionBufWriter<'a> {
+        let capacity = region.buffer_size();
+        RegionBufWriter::with_capacity(region, capacity)
+    }
+
+    /// Creates a new `RegionBufWriter` that buffers up to `capacity` bytes.
+    pub fn with_capacity(region: FileRegion<'a>, capacity: usize) -> RegionBufWriter<'a> {
+        RegionBufWriter {
+            region,
+            buf: Vec::with_capacity(capacity),
+            start: 0,
+        }
+    }
+
+    /// Returns the underlying region.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Returns the buffer's capacity.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns the bytes buffered but not yet written.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Buffers a write of `buf` at `offset`, with the bounds semantics of
+    /// `FileRegion::write()`. Returns `buf.len()`.
+    ///
+    /// May return an I/O error from writing out earlier buffered bytes.
+    pub fn write(&mut self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.region.range, offset..end)?;
+        let contiguous = offset == self.start + self.buf.len() as u64;
+        if !contiguous || self.buf.len() + buf.len() > self.buf.capacity() {
+            self.flush()?;
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.region.write_full(offset, buf)?;
+        } else {
+            if self.buf.is_empty() {
+                self.start = offset;
+            }
+            self.buf.extend_from_slice(buf);
+        }
+        Ok(buf.len())
+    }
+
+    /// Writes out any buffered bytes.
+    pub fn flush(&mut self) -> Result<(), FileRegionError> {
+        if !self.buf.is_empty() {
+            self.region.write_full(self.start, &self.buf)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+
+    /// Flushes the buffer and returns the underlying region.
+    pub fn into_inner(mut self) -> Result<FileRegion<'a>, FileRegionError> {
+        self.flush()?;
+        Ok(self.region.with_range(self.region.range.clone()))
+    }
+}
+
+impl Drop for RegionBufWriter<'_> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
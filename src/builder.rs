@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::ops::Range;
+
+use super::core::{validate_range, FileRegion, COPY_BUF_SIZE};
+use super::error::{FileRegionError, RegionError};
+use super::oob::OobPolicy;
+
+/// When a `RegionBuilder` checks the range against the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// Check the range against the file's length in `build()`.
+    Eager,
+    /// Skip the check; use `FileRegion::validate()` later, as with
+    /// `FileRegion::new()`.
+    Lazy,
+}
+
+/// Configures the construction of a `FileRegion`. Create one with
+/// `FileRegion::builder()`.
+///
+/// By default the region spans the entire file and is validated eagerly.
+pub struct RegionBuilder<'a> {
+    file: &'a File,
+    range: Option<Range<u64>>,
+    validation: Validation,
+    clamp: bool,
+    alignment: Option<u64>,
+    buf_size: usize,
+    oob: OobPolicy,
+}
+
+impl<'a> RegionBuilder<'a> {
+    /// Creates a new `RegionBuilder` for `file` with the default options.
+    pub fn new(file: &'a File) -> RegionBuilder<'a> {
+        RegionBuilder {
+            file,
+            range: None,
+            validation: Validation::Eager,
+            clamp: false,
+            alignment: None,
+            buf_size: COPY_BUF_SIZE,
+            oob: OobPolicy::default(),
+        }
+    }
+
+    /// Sets the range of the region. Defaults to the entire file.
+    pub fn range(mut self, range: Range<u64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Sets when the range is validated against the file. Defaults to
+    /// `Validation::Eager`.
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// If `clamp` is true, shrinks the range to fit within the file's current
+    /// length instead of failing validation. Defaults to false.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Requires both ends of the range to be multiples of `block`, failing
+    /// with `RegionError::Misaligned` otherwise. The check applies after
+    /// clamping.
+    ///
+    /// Panics if `block` is zero.
+    pub fn align(mut self, block: u64) -> Self {
+        assert!(block != 0, "alignment must be non-zero");
+        self.alignment = Some(block);
+        self
+    }
+
+    /// Sets the size of the buffer used by the region's streaming operations;
+    /// see `FileRegion::buffer_size()`. Defaults to 8 KiB.
+    ///
+    /// Panics if `size` is zero.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        assert!(size != 0, "buffer size must be non-zero");
+        self.buf_size = size;
+        self
+    }
+
+    /// Sets how the region treats reads and writes that cross its end.
+    /// Defaults to `OobPolicy::ClampReads`.
+    pub fn oob_policy(mut self, policy: OobPolicy) -> Self {
+        self.oob = policy;
+        self
+    }
+
+    /// Builds the `FileRegion`. Performs I/O to get the file's length only if
+    /// the options require it: no explicit range, clamping, or eager
+    /// validation.
+    pub fn build(self) -> Result<FileRegion<'a>, FileRegionError> {
+        let needs_len = self.range.is_none() || self.clamp || self.validation == Validation::Eager;
+        let len = if needs_len {
+            Some(self.file.metadata().map_err(FileRegionError::Io)?.len())
+        } else {
+            None
+        };
+        let mut range = match (self.range, len) {
+            (Some(range), _) => range,
+            (None, Some(len)) => 0..len,
+            (None, None) => unreachable!(),
+        };
+        if let (true, Some(len)) = (self.clamp, len) {
+            range.end = range.end.min(len);
+            range.start = range.start.min(range.end);
+        }
+        if let Some(block) = self.alignment {
+            if !range.start.is_multiple_of(block) || !range.end.is_multiple_of(block) {
+                return Err(FileRegionError::Region(RegionError::Misaligned));
+            }
+        }
+        if let (Validation::Eager, Some(len)) = (self.validation, len) {
+            validate_range(&range, len).map_err(FileRegionError::Region)?;
+        }
+        let mut region = FileRegion::new(self.file, range);
+        region.buf_size = self.buf_size;
+        region.oob = self.oob;
+        Ok(region)
+    }
+}
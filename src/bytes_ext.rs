@@ -0,0 +1,58 @@
+use bytes::{Bytes, BytesMut};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Reads up to `len` bytes starting at `offset` into a new `Bytes`. Stops
+    /// early at the end of the region or the end of the underlying file, so
+    /// the result may be shorter than `len`.
+    ///
+    /// Has the same bounds checks as `read()`, even when `len` is zero, and
+    /// also returns an error if `len` does not fit in `usize`.
+    pub fn read_bytes(&self, offset: u64, len: u64) -> Result<Bytes, FileRegionError> {
+        let len = usize::try_from(len).map_err(|_| RegionError::EndOverflow)?;
+        let len = self.read_len(offset, len)?;
+        let mut buf = BytesMut::zeroed(len);
+        let n = self.read_full(offset, &mut buf)?;
+        buf.truncate(n);
+        Ok(buf.freeze())
+    }
+
+    /// Appends the bytes from `offset` to the end of the region onto `dst`,
+    /// reserving space as needed. Returns the number of bytes appended, which
+    /// may be less than the remaining region length if the underlying file is
+    /// shorter.
+    ///
+    /// Returns an error if the read starts beyond the region.
+    pub fn read_into(&self, offset: u64, dst: &mut BytesMut) -> Result<usize, FileRegionError> {
+        if offset >= self.len() {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len(),
+            }));
+        }
+        let remaining =
+            usize::try_from(self.len() - offset).map_err(|_| RegionError::EndOverflow)?;
+        let start = dst.len();
+        dst.resize(start + remaining, 0);
+        match self.read_full(offset, &mut dst[start..]) {
+            Ok(n) => {
+                dst.truncate(start + n);
+                Ok(n)
+            }
+            Err(error) => {
+                dst.truncate(start);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+    /// Writes `data` at `offset`. Has the same bounds semantics as `write()`.
+    pub fn write_bytes(&self, offset: u64, data: &Bytes) -> Result<usize, FileRegionError> {
+        self.write(offset, data)
+    }
+}
@@ -0,0 +1,37 @@
+use std::io::Result as IoResult;
+
+use cap_std::fs::{File, FileExt};
+
+use super::backend::{RegionBackend, WriteBackend};
+
+/// Lets capability-sandboxed programs bound a `cap_std::fs::File` with a
+/// `FileRegion`, without converting it to a `std::fs::File`. Uses
+/// `read_at`/`write_at` on Unix and WASI, and `seek_read`/`seek_write` on
+/// Windows.
+impl RegionBackend for File {
+    #[cfg(not(windows))]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        FileExt::read_at(self, buf, offset)
+    }
+
+    #[cfg(windows)]
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        FileExt::seek_read(self, buf, offset)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        Ok(self.metadata()?.len())
+    }
+}
+
+impl WriteBackend for File {
+    #[cfg(not(windows))]
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        FileExt::write_at(self, buf, offset)
+    }
+
+    #[cfg(windows)]
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        FileExt::seek_write(self, buf, offset)
+    }
+}
@@ -0,0 +1,66 @@
+use super::backend::RegionBackend;
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Returns the number of leading bytes this region shares with `other`,
+    /// which may be over a different backend. Streams both regions in chunks,
+    /// stopping at the first difference or at the end of the shorter one. A
+    /// region whose file ends early is compared only up to the end of the
+    /// file.
+    pub fn common_prefix_len<C: RegionBackend + ?Sized>(
+        &self,
+        other: &FileRegion<'_, C>,
+    ) -> Result<u64, FileRegionError> {
+        Ok(self.compare_prefix(other)?.0)
+    }
+
+    /// Returns the offset of the first byte at which this region and `other`
+    /// differ, or `None` if their contents are identical. If one is a proper
+    /// prefix of the other, they differ at the end of the shorter one.
+    pub fn first_mismatch<C: RegionBackend + ?Sized>(
+        &self,
+        other: &FileRegion<'_, C>,
+    ) -> Result<Option<u64>, FileRegionError> {
+        let (prefix, same) = self.compare_prefix(other)?;
+        Ok((!same).then_some(prefix))
+    }
+
+    /// Returns the common prefix length and whether both regions ended there.
+    fn compare_prefix<C: RegionBackend + ?Sized>(
+        &self,
+        other: &FileRegion<'_, C>,
+    ) -> Result<(u64, bool), FileRegionError> {
+        let mut ours = vec![0; self.buf_size];
+        let mut theirs = vec![0; self.buf_size];
+        let mut offset = 0;
+        loop {
+            let a = read_chunk(self, offset, &mut ours)?;
+            let b = read_chunk(other, offset, &mut theirs)?;
+            let n = a.min(b);
+            if let Some(i) = ours[..n].iter().zip(&theirs[..n]).position(|(x, y)| x != y) {
+                return Ok((offset + i as u64, false));
+            }
+            offset += n as u64;
+            if a != b {
+                return Ok((offset, false));
+            }
+            if n == 0 {
+                return Ok((offset, true));
+            }
+        }
+    }
+}
+
+/// Reads as much of `buf` as is available at `offset`, returning 0 at the end
+/// of the region.
+fn read_chunk<B: RegionBackend + ?Sized>(
+    region: &FileRegion<'_, B>,
+    offset: u64,
+    buf: &mut [u8],
+) -> Result<usize, FileRegionError> {
+    if offset >= region.len() {
+        return Ok(0);
+    }
+    region.read_full(offset, buf)
+}
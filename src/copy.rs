@@ -0,0 +1,41 @@
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+use super::positional;
+
+impl FileRegion<'_> {
+    /// Copies the bytes of this region to the start of `dst`, which may be in
+    /// the same file or a different one. Regions over the same `File` may
+    /// overlap: the copy has `memmove` semantics, so the result is as if the
+    /// source had been read in full before anything was written. Copies
+    /// through a bounded buffer, one chunk at a time. Returns the number of
+    /// bytes copied, which is always `len()`.
+    ///
+    /// Overlap is detected by `File` handle, as with `overlaps()`; two
+    /// separately opened handles to the same file are treated as different
+    /// files.
+    ///
+    /// Returns `RegionError::LengthMismatch` if `dst` is shorter than this
+    /// region. May return an I/O error from reading or writing (including
+    /// `UnexpectedEof` if the file ends before this region does).
+    pub fn copy_to(&self, dst: &FileRegion) -> Result<u64, FileRegionError> {
+        let len = self.len();
+        if dst.len() < len {
+            return Err(FileRegionError::Region(RegionError::LengthMismatch));
+        }
+        if std::ptr::eq(self.file, dst.file) {
+            if self.range.start != dst.range.start {
+                positional::move_within(self.file, self.range.start, dst.range.start, len)?;
+            }
+            return Ok(len);
+        }
+        let mut buf = vec![0; dst.buf_size.min(usize::try_from(len).unwrap_or(usize::MAX))];
+        let mut offset = 0;
+        while offset < len {
+            let chunk = &mut buf[..(len - offset).min(dst.buf_size as u64) as usize];
+            positional::read_exact_at(self.file, chunk, self.range.start + offset)?;
+            positional::write_all_at(dst.file, chunk, dst.range.start + offset)?;
+            offset += chunk.len() as u64;
+        }
+        Ok(len)
+    }
+}
@@ -1,52 +1,228 @@
+use std::fmt;
 use std::fs::{File, Metadata};
+use std::hash::{Hash, Hasher};
 use std::io::Result as IoResult;
-use std::io::{Read, Seek, SeekFrom, Write};
-use std::ops::Range;
+use std::io::{ErrorKind, Read, Write};
+use std::mem::MaybeUninit;
+use std::ops::{Bound, Range, RangeBounds};
+use std::sync::Arc;
 
-use super::error::{FileRegionError, RegionError};
+use super::backend::{RegionBackend, WriteBackend};
+use super::builder::RegionBuilder;
+use super::error::{FileRegionError, RegionError, ShortReadError};
+use super::oob::OobPolicy;
+use super::positional;
+use super::stats::{self, RegionStats, StatsSnapshot};
 
-pub struct FileRegion<'a> {
-    file: &'a File,
+/// Size of the internal buffer used by streaming operations.
+pub(crate) const COPY_BUF_SIZE: usize = 8 * 1024;
+
+/// Minimum size of the buffer used to scan a region for a byte pattern, such
+/// as a needle or a line break.
+pub(crate) const SCAN_BUF_SIZE: usize = 8 * 1024;
+
+/// A bounded region of a `File`, or of any other `RegionBackend`. Reads,
+/// writes, and subregions work over every backend; operations that need a
+/// real file are only available on regions over a `File`.
+pub struct FileRegion<'a, B: ?Sized = File> {
+    pub(crate) file: &'a B,
+    pub(crate) range: Range<u64>,
+    /// Absolute range of the region this one was carved from, if any.
+    pub(crate) parent_range: Option<Range<u64>>,
+    /// Whether subregions record their full ancestry; see `with_ancestry()`.
+    pub(crate) track_ancestry: bool,
+    pub(crate) parent: Option<Arc<Parent>>,
+    pub(crate) stats: Option<Arc<RegionStats>>,
+    /// Position of the `Read`, `Write`, and `Seek` cursor, relative to the
+    /// start of the region.
+    pub(crate) pos: u64,
+    /// Size of the buffer used by streaming operations.
+    pub(crate) buf_size: usize,
+    pub(crate) oob: OobPolicy,
+}
+
+impl<B: ?Sized> Clone for FileRegion<'_, B> {
+    fn clone(&self) -> Self {
+        FileRegion {
+            file: self.file,
+            range: self.range.clone(),
+            parent_range: self.parent_range.clone(),
+            track_ancestry: self.track_ancestry,
+            parent: self.parent.clone(),
+            stats: self.stats.clone(),
+            pos: self.pos,
+            buf_size: self.buf_size,
+            oob: self.oob,
+        }
+    }
+}
+
+/// Shows the file and range. The alternate form (`{:#?}`) also shows the
+/// length, the ancestry if tracked, the statistics if enabled, the cursor, the
+/// buffer size, and the out-of-bounds policy.
+impl<B: RegionBackend + fmt::Debug + ?Sized> fmt::Debug for FileRegion<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("FileRegion");
+        debug.field("file", &self.file).field("range", &self.range);
+        if alternate {
+            debug
+                .field("len", &self.len())
+                .field("ancestors", &self.ancestors().collect::<Vec<_>>())
+                .field("stats", &self.stats())
+                .field("pos", &self.pos)
+                .field("buf_size", &self.buf_size)
+                .field("oob", &self.oob);
+        }
+        debug.finish()
+    }
+}
+
+/// Renders the range and length, as in `FileRegion(1024..4096, len=3072)`.
+impl<B: RegionBackend + ?Sized> fmt::Display for FileRegion<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FileRegion({:?}, len={})", self.range, self.len())
+    }
+}
+
+/// Regions are equal if they are over the same `File` (the same handle, not
+/// merely the same path) and have the same range. The ancestry, statistics,
+/// and cursor are ignored.
+impl<B: RegionBackend + ?Sized> PartialEq for FileRegion<'_, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_file(other) && self.range == other.range
+    }
+}
+
+impl<B: RegionBackend + ?Sized> Eq for FileRegion<'_, B> {}
+
+/// Hashes the file handle's address and the range, consistent with `Eq`.
+impl<B: RegionBackend + ?Sized> Hash for FileRegion<'_, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.file, state);
+        self.range.hash(state);
+    }
+}
+
+/// One link in the chain of regions that a subregion was carved from, kept
+/// only when ancestry tracking is enabled.
+pub(crate) struct Parent {
     range: Range<u64>,
+    parent: Option<Arc<Parent>>,
 }
 
-impl<'a> FileRegion<'a> {
-    /// Creates a new `FileRegion`. Note that `range` is _not_ validated against
+impl<'a, B: RegionBackend + ?Sized> FileRegion<'a, B> {
+    /// Creates a new `FileRegion` over `file`, which is usually a `File` but
+    /// may be any `RegionBackend`. Note that `range` is _not_ validated against
     /// the `file`. Use `is_valid()` or `validate()` to check consistency.
-    pub fn new(file: &File, range: Range<u64>) -> FileRegion {
-        FileRegion { file, range }
+    /// Takes a plain `Range` because it performs no I/O, so it cannot resolve
+    /// an unbounded end; `try_new()` accepts any range type.
+    pub fn new(file: &'a B, range: Range<u64>) -> FileRegion<'a, B> {
+        FileRegion {
+            file,
+            range,
+            parent_range: None,
+            track_ancestry: false,
+            parent: None,
+            stats: None,
+            pos: 0,
+            buf_size: COPY_BUF_SIZE,
+            oob: OobPolicy::default(),
+        }
     }
 
     /// Creates a new `FileRegion`, validating the `range` against the `file`.
     /// Returns `Ok(FileRegion)` if valid. Otherwise, returns a
     /// `FileRegionError` due to invalid range or I/O errors during
     /// validation.
-    pub fn try_new(file: &'a File, range: Range<u64>) -> Result<FileRegion, FileRegionError> {
+    ///
+    /// Accepts any range type, such as `10..`, `..=99`, or `..`; an unbounded
+    /// end resolves to the length of the file.
+    pub fn try_new(
+        file: &'a B,
+        range: impl RangeBounds<u64>,
+    ) -> Result<FileRegion<'a, B>, FileRegionError> {
+        let range = match range.end_bound() {
+            Bound::Unbounded => resolve_range(&range, file.len()?)?,
+            _ => resolve_range(&range, u64::MAX)?,
+        };
         let region = FileRegion::new(file, range);
         region.validate()?;
         Ok(region)
     }
 
-    /// Creates a new `FileRegion` spanning the entire `file`. Validity is
-    /// guaranteed.
-    pub fn from_file(file: &'a File) -> IoResult<Self> {
-        let range = 0..file.metadata()?.len();
-        Ok(FileRegion { file, range })
+    /// Enables I/O instrumentation for this region, with counters starting at
+    /// zero. Subregions and other regions derived from this one share its
+    /// counters, so `stats()` covers all I/O made through any of them.
+    ///
+    /// Only `read()`, `write()`, `write_at()`, and the operations
+    /// built on them are counted.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(Arc::default());
+        self
     }
 
-    /// Returns the file metadata.
-    pub fn file_metadata(&self) -> IoResult<Metadata> {
-        self.file.metadata()
+    /// Enables ancestry tracking: subregions derived from this region record
+    /// the full chain of regions they were carved from, as reported by
+    /// `ancestors()`. Off by default, since each derived region then
+    /// allocates a link in the chain.
+    pub fn with_ancestry(mut self) -> Self {
+        self.track_ancestry = true;
+        self
+    }
+
+    /// Returns a snapshot of the I/O counters, or `None` if instrumentation
+    /// was not enabled with `with_stats()`.
+    pub fn stats(&self) -> Option<StatsSnapshot> {
+        self.stats.as_ref().map(|stats| stats.snapshot())
     }
 
-    /// Return the region (a range).
-    pub fn range(self) -> Range<u64> {
+    /// Resets the I/O counters (shared with derived regions) to zero. Does
+    /// nothing if instrumentation is not enabled.
+    pub fn reset_stats(&self) {
+        if let Some(stats) = &self.stats {
+            stats.reset();
+        }
+    }
+
+    /// Returns the size of the buffer used by streaming operations such as
+    /// `copy_to_writer()`, `fill_from_reader()`, and `buf_reader()`. Defaults
+    /// to 8 KiB; set it with `RegionBuilder::buffer_size()`. Regions derived
+    /// from this one inherit it.
+    pub fn buffer_size(&self) -> usize {
+        self.buf_size
+    }
+
+    /// Returns the region (a range) without consuming the region.
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns a reference to the region's range, for comparing or logging
+    /// bounds without cloning.
+    pub fn range_ref(&self) -> &Range<u64> {
+        &self.range
+    }
+
+    /// Returns the absolute file offset where the region starts.
+    pub fn start(&self) -> u64 {
+        self.range.start
+    }
+
+    /// Returns the absolute file offset where the region ends (exclusive).
+    pub fn end(&self) -> u64 {
+        self.range.end
+    }
+
+    /// Consumes the region, returning its range.
+    pub fn into_range(self) -> Range<u64> {
         self.range
     }
 
-    /// Returns the length of the region in bytes.
+    /// Returns the length of the region in bytes; 0 if the (unvalidated)
+    /// range starts after it ends.
     pub fn len(&self) -> u64 {
-        self.range.end - self.range.start
+        self.range.end.saturating_sub(self.range.start)
     }
 
     /// Returns if the region is empty (zero length).
@@ -58,10 +234,7 @@ impl<'a> FileRegion<'a> {
     /// Returns true if the range is within the file's bounds, false otherwise.
     /// Performs I/O to get the file's metadata.
     pub fn is_valid(&self) -> IoResult<bool> {
-        let metadata = self.file.metadata()?;
-        let len = metadata.len();
-        // Note the careful usage of `<` and `<=`.
-        Ok(self.range.start < len && self.range.end <= len)
+        Ok(validate_range(&self.range, self.file.len()?).is_ok())
     }
 
     /// Validates the `FileRegion` by checking if its range is within the bounds
@@ -69,8 +242,7 @@ impl<'a> FileRegion<'a> {
     /// `FileRegionError` detailing the specific validation failure or I/O error
     /// encountered.
     pub fn validate(&self) -> Result<(), FileRegionError> {
-        let metadata = self.file.metadata().map_err(FileRegionError::Io)?;
-        let len = metadata.len();
+        let len = self.file.len().map_err(FileRegionError::Io)?;
         validate_range(&self.range, len).map_err(FileRegionError::Region)
     }
 
@@ -82,80 +254,685 @@ impl<'a> FileRegion<'a> {
     /// buffer's capacity.
     ///
     /// If the read begin inside the region, no error is returned, even if the
-    /// read attempts to go past the end of the region.
+    /// read attempts to go past the end of the region. (That is the default;
+    /// see `OobPolicy` for the alternatives.)
     ///
     /// On the other hand, if the read attempts to start beyond the region,
     /// returns an error.
     ///
     /// Returns an error if `offset: u64` is too large to fit in `usize`.
     ///
-    /// May return an I/O error from seeking or reading.
-    pub fn read(&mut self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+    /// Uses positional I/O (`pread` on Unix, `seek_read` on Windows), so it
+    /// only needs `&self`, leaves the file's cursor alone on Unix, and can be
+    /// called from several threads at once.
+    ///
+    /// May return an I/O error from reading.
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        let len = self.read_len(offset, buf.len())?;
+        let buf = &mut buf[..len];
+        let n = self.read_shared(offset, buf)?;
+        self.finish_read(offset, buf, n)
+    }
+
+    /// The body of `read()`, always clamping to the end of the region
+    /// whatever the policy.
+    pub(crate) fn read_shared(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize, FileRegionError> {
+        let range = clamped_subrange(&self.range, offset, buf.len())?;
+        let buf = &mut buf[..(range.end - range.start) as usize];
+        stats::record_read(self.stats.as_deref(), || {
+            self.file.read_at(range.start, buf)
+        })
+        .map_err(FileRegionError::Io)
+    }
+
+    /// Reads repeatedly from `offset` until `buf` is full, the end of the
+    /// region is reached, or the underlying file has no more data. Returns the
+    /// number of bytes read. Like `read()`, returns an error if `offset` starts
+    /// beyond the region.
+    pub(crate) fn read_full(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let pos = offset.saturating_add(filled as u64);
+            if filled > 0 && pos >= self.len() {
+                break;
+            }
+            match self.read_shared(pos, &mut buf[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+        Ok(filled)
+    }
+
+    /// Reads exactly `buf.len()` bytes starting at `offset`, retrying short
+    /// reads until `buf` is full.
+    ///
+    /// Returns a region error, reading nothing, if the bytes do not all lie
+    /// within the region. Returns `FileRegionError::ShortRead` if the
+    /// underlying file ends first; the bytes that were available are left
+    /// at the start of `buf`.
+    pub fn read_exact(&self, offset: u64, buf: &mut [u8]) -> Result<(), FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.range, offset..end)?;
+        let read = self.read_full(offset, buf)?;
+        if read < buf.len() {
+            return Err(FileRegionError::ShortRead(ShortReadError {
+                requested: buf.len(),
+                read,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Reads the whole region into a new `Vec`, allocated up front from
+    /// `len()`. The `Vec` is shorter than `len()` only if the underlying file
+    /// ends before the region does.
+    pub fn read_to_vec(&self) -> Result<Vec<u8>, FileRegionError> {
+        let mut buf = Vec::new();
+        self.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Appends the whole region to `buf`, reserving `len()` bytes first.
+    /// Returns the number of bytes appended, which is less than `len()` only
+    /// if the underlying file ends before the region does. Unlike
+    /// `std::io::Read::read_to_end`, always reads from the start of the
+    /// region and ignores the cursor.
+    pub fn read_to_end(&self, buf: &mut Vec<u8>) -> Result<usize, FileRegionError> {
+        let len = usize::try_from(self.len()).map_err(|_| RegionError::EndOverflow)?;
+        if len == 0 {
+            return Ok(0);
+        }
+        let old = buf.len();
+        buf.resize(old + len, 0);
+        let result = self.read_full(0, &mut buf[old..]);
+        let n = *result.as_ref().unwrap_or(&0);
+        buf.truncate(old + n);
+        result
+    }
+
+    /// Reads the whole region into a `String`, as `read_to_vec()` does.
+    /// Returns `FileRegionError::Utf8` if the bytes are not valid UTF-8; the
+    /// bytes can be recovered from the error.
+    pub fn read_to_string(&self) -> Result<String, FileRegionError> {
+        String::from_utf8(self.read_to_vec()?).map_err(FileRegionError::Utf8)
+    }
+
+    /// Reads exactly `N` bytes starting at `offset` into an array.
+    ///
+    /// Has the same error semantics as `read_exact()`.
+    pub fn read_array<const N: usize>(&self, offset: u64) -> Result<[u8; N], FileRegionError> {
+        let mut buf = [0; N];
+        self.read_exact(offset, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Streams the entire region into `w`. Returns the number of bytes
+    /// copied, which is less than `len()` only if the underlying file ends
+    /// before the region does.
+    ///
+    /// May return an I/O error from reading or writing.
+    pub fn copy_to_writer(&self, w: &mut impl Write) -> Result<u64, FileRegionError> {
+        let mut buf = vec![0; self.buf_size];
+        let mut offset = 0;
+        while offset < self.len() {
+            let n = self.read_full(offset, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            w.write_all(&buf[..n]).map_err(FileRegionError::Io)?;
+            offset += n as u64;
+        }
+        Ok(offset)
+    }
+
+    /// Like `read()`, but first re-validates the region against the file's
+    /// current length, so a file truncated after the region was created
+    /// produces `RegionError::EndOutOfBounds` (or `StartOutOfBounds`) instead
+    /// of a short read. Performs I/O to get the file's metadata on every
+    /// call.
+    pub fn checked_read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        self.validate()?;
+        self.read(offset, buf)
+    }
+
+    /// Return a subregion borrowing the same file; the parent region remains
+    /// usable. Checks for some inconsistencies but not all; use `is_valid()`
+    /// to check consistency against the underlying file.
+    ///
+    /// The subregion remembers the range it was carved from (see
+    /// `relative_range()`) and, if ancestry tracking is enabled, the whole
+    /// chain (see `ancestors()`).
+    ///
+    /// Accepts any range type, relative to this region: `..100`, `10..`, and
+    /// `2..=5` all work, and unbounded ends resolve to this region's bounds.
+    pub fn subregion(
+        &self,
+        range: impl RangeBounds<u64>,
+    ) -> Result<FileRegion<'a, B>, RegionError> {
+        let range = resolve_range(&range, self.len())?;
+        Ok(self.child(subrange(&self.range, range)?))
+    }
+
+    /// Returns a subregion with the given absolute `range`, recording this
+    /// region as its parent. Does not check the range.
+    pub(crate) fn child(&self, range: Range<u64>) -> FileRegion<'a, B> {
+        FileRegion {
+            file: self.file,
+            range,
+            parent_range: Some(self.range.clone()),
+            track_ancestry: self.track_ancestry,
+            parent: self.track_ancestry.then(|| {
+                Arc::new(Parent {
+                    range: self.range.clone(),
+                    parent: self.parent.clone(),
+                })
+            }),
+            stats: self.stats.clone(),
+            pos: 0,
+            buf_size: self.buf_size,
+            oob: self.oob,
+        }
+    }
+
+    /// Returns true if both ends of the region are multiples of `block`.
+    ///
+    /// Panics if `block` is zero.
+    pub fn is_aligned(&self, block: u64) -> bool {
+        assert!(block != 0, "alignment must be non-zero");
+        self.range.start.is_multiple_of(block) && self.range.end.is_multiple_of(block)
+    }
+
+    /// Returns the smallest region that contains this one and whose ends are
+    /// multiples of `block`: the start rounds down and the end rounds up.
+    ///
+    /// Returns `RegionError::EndOverflow` if rounding up the end overflows.
+    /// Panics if `block` is zero.
+    pub fn align_to(&self, block: u64) -> Result<FileRegion<'a, B>, RegionError> {
+        assert!(block != 0, "alignment must be non-zero");
+        let start = self.range.start - self.range.start % block;
+        let end = self
+            .range
+            .end
+            .checked_next_multiple_of(block)
+            .ok_or(RegionError::EndOverflow)?;
+        Ok(self.with_range(start..end))
+    }
+
+    /// Returns the largest region within this one whose ends are multiples of
+    /// `block`: the start rounds up and the end rounds down.
+    ///
+    /// Returns `RegionError::Misaligned` if no block boundary pair fits inside
+    /// the region, or `RegionError::StartOverflow` if rounding up the start
+    /// overflows. Panics if `block` is zero.
+    pub fn align_inward(&self, block: u64) -> Result<FileRegion<'a, B>, RegionError> {
+        assert!(block != 0, "alignment must be non-zero");
         let start = self
             .range
+            .start
+            .checked_next_multiple_of(block)
+            .ok_or(RegionError::StartOverflow)?;
+        let end = self.range.end - self.range.end % block;
+        if start > end {
+            return Err(RegionError::Misaligned);
+        }
+        Ok(self.with_range(start..end))
+    }
+
+    /// Returns true if `other` is over the same `File` and the two regions
+    /// share at least one byte. Empty regions overlap nothing.
+    pub fn overlaps(&self, other: &FileRegion<'_, B>) -> bool {
+        self.same_file(other)
+            && self.range.start < other.range.end
+            && other.range.start < self.range.end
+    }
+
+    /// Returns true if `other` is over the same `File` and lies entirely
+    /// within this region.
+    pub fn contains_region(&self, other: &FileRegion<'_, B>) -> bool {
+        self.same_file(other)
+            && self.range.start <= other.range.start
+            && other.range.end <= self.range.end
+    }
+
+    /// Returns the bytes shared by both regions, or `None` if they do not
+    /// overlap.
+    pub fn intersection(&self, other: &FileRegion<'_, B>) -> Option<FileRegion<'a, B>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let start = self.range.start.max(other.range.start);
+        let end = self.range.end.min(other.range.end);
+        Some(self.with_range(start..end))
+    }
+
+    /// Returns the smallest region covering both regions. Returns
+    /// `RegionError::Disjoint` if they are over different files or are
+    /// separated by a gap; adjacent regions can be joined.
+    pub fn union(&self, other: &FileRegion<'_, B>) -> Result<FileRegion<'a, B>, RegionError> {
+        let touching = self.range.start <= other.range.end && other.range.start <= self.range.end;
+        if !self.same_file(other) || !touching {
+            return Err(RegionError::Disjoint);
+        }
+        let start = self.range.start.min(other.range.start);
+        let end = self.range.end.max(other.range.end);
+        Ok(self.with_range(start..end))
+    }
+
+    /// Returns the parts of this region not covered by `other`, in order:
+    /// none, one, or two non-empty regions.
+    pub fn difference(&self, other: &FileRegion<'_, B>) -> Vec<FileRegion<'a, B>> {
+        if !self.overlaps(other) {
+            return if self.is_empty() {
+                Vec::new()
+            } else {
+                vec![self.with_range(self.range.clone())]
+            };
+        }
+        let before = self.range.start..other.range.start;
+        let after = other.range.end..self.range.end;
+        [before, after]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| self.with_range(range))
+            .collect()
+    }
+
+    fn same_file(&self, other: &FileRegion<'_, B>) -> bool {
+        std::ptr::eq(self.file, other.file)
+    }
+
+    /// Returns a region over the same file with the given absolute `range`,
+    /// sharing this region's ancestry if `range` lies within its parent. A
+    /// range that extends outside the parent, as `align_to()` or `union()`
+    /// may produce, has no parent.
+    pub(crate) fn with_range(&self, range: Range<u64>) -> FileRegion<'a, B> {
+        let inside = self
+            .parent_range
+            .as_ref()
+            .is_some_and(|parent| parent.start <= range.start && range.end <= parent.end);
+        FileRegion {
+            file: self.file,
+            range,
+            parent_range: self.parent_range.clone().filter(|_| inside),
+            track_ancestry: self.track_ancestry,
+            parent: self.parent.clone().filter(|_| inside),
+            stats: self.stats.clone(),
+            pos: 0,
+            buf_size: self.buf_size,
+            oob: self.oob,
+        }
+    }
+
+    /// Returns the region's range in terms of absolute file offsets. The same
+    /// as `range()`; named for symmetry with `relative_range()`.
+    pub fn absolute_range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns the region's range relative to the region it was carved from,
+    /// or the absolute range if it was not created by `subregion()` or has
+    /// since been extended outside its parent.
+    pub fn relative_range(&self) -> Range<u64> {
+        match &self.parent_range {
+            Some(parent) => {
+                let start = parent.start;
+                (self.range.start - start)..(self.range.end - start)
+            }
+            None => self.absolute_range(),
+        }
+    }
+
+    /// Maps `offset`, relative to this region, to an absolute file offset.
+    /// `offset` may equal `len()`, which maps to the end of the region.
+    pub fn absolute_offset(&self, offset: u64) -> Result<u64, RegionError> {
+        if offset > self.len() {
+            return Err(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len(),
+            });
+        }
+        self.range
             .start
             .checked_add(offset)
-            .ok_or(FileRegionError::Region(RegionError::StartOverflow))?;
-        if start >= self.range.end {
-            return Err(FileRegionError::Region(RegionError::StartOutOfBounds));
+            .ok_or(RegionError::StartOverflow)
+    }
+
+    /// Returns this region's start offset relative to `ancestor`, or `None`
+    /// if this region does not lie within `ancestor` in the same file.
+    /// `ancestor` does not need to appear in `ancestors()`; only the ranges
+    /// are compared.
+    pub fn offset_in(&self, ancestor: &FileRegion<'_, B>) -> Option<u64> {
+        let within =
+            ancestor.range.start <= self.range.start && self.range.end <= ancestor.range.end;
+        if self.same_file(ancestor) && within {
+            Some(self.range.start - ancestor.range.start)
+        } else {
+            None
         }
-        self.file
-            .seek(SeekFrom::Start(start))
-            .map_err(FileRegionError::Io)?;
-        let limit = self.len().saturating_sub(offset);
-        (&mut self.file)
-            .take(limit)
-            .read(buf)
-            .map_err(FileRegionError::Io)
     }
 
-    /// Attempts to perform a bounded write operation within the file region.
-    ///
-    /// Returns the number of bytes successfully written.
+    /// Returns the absolute ranges of the regions this region was carved
+    /// from via `subregion()`, starting with the immediate parent and ending
+    /// with the outermost region. Empty unless ancestry tracking was enabled
+    /// with `with_ancestry()` on this region or one it was derived from;
+    /// only ancestors created after that are recorded.
+    pub fn ancestors(&self) -> impl Iterator<Item = &Range<u64>> {
+        std::iter::successors(self.parent.as_deref(), |parent| parent.parent.as_deref())
+            .map(|parent| &parent.range)
+    }
+}
+
+impl<'a, B: WriteBackend + ?Sized> FileRegion<'a, B> {
+    /// Performs a bounded write operation within the file region, writing all
+    /// of `buf`. Returns the number of bytes written, which on success is
+    /// always `buf.len()`; see `write_at()` for a single write call that may
+    /// write less.
     ///
     /// If any part of the write are out-of-bounds, write nothing and return an
     /// error. There are two out-of-bound cases:
     /// - start the write in the region that is too long
     /// - start the write beyond the region
     ///
-    /// Returns an error if `offset: u64` is too large to fit in `usize`.
+    /// Like `read()`, uses positional I/O and only needs `&self`.
+    ///
+    /// May return an I/O error from writing, in which case some prefix of
+    /// `buf` may have been written.
+    pub fn write(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let buf = &buf[..self.write_len(offset, buf.len())];
+        self.write_full(offset, buf)?;
+        Ok(buf.len())
+    }
+
+    /// Writes all of `buf` at `offset`, retrying partial writes until done,
+    /// or nothing if it does not fit. Unlike `write()`, never clamps: fails
+    /// with `RegionError::EndOutOfBounds` under every `OobPolicy`, including
+    /// `OobPolicy::Clamp`, if `buf` extends past the end of the region.
+    pub fn write_all(&self, offset: u64, buf: &[u8]) -> Result<(), FileRegionError> {
+        self.write_full(offset, buf)
+    }
+
+    /// Performs a bounded positional write within the file region using a
+    /// single write call, named for symmetry with `FileExt::write_at`.
+    ///
+    /// Returns the number of bytes successfully written, which (as with
+    /// `std::io::Write::write`) may be less than `buf.len()` even though the
+    /// whole buffer fits in the region.
+    ///
+    /// Has the same bounds semantics as `write()`.
+    ///
+    /// May return an I/O error from writing.
+    pub fn write_at(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let buf = &buf[..self.write_len(offset, buf.len())];
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        let range = subrange(&self.range, offset..end)?;
+        stats::record_write(self.stats.as_deref(), || {
+            self.file.write_at(range.start, buf)
+        })
+        .map_err(FileRegionError::Io)
+    }
+
+    /// Writes repeatedly until all of `buf` has been written.
+    /// Checks the bounds of the whole write first, so nothing is written if
+    /// any part is out-of-bounds.
+    pub(crate) fn write_full(&self, offset: u64, buf: &[u8]) -> Result<(), FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        let range = subrange(&self.range, offset..end)?;
+        positional::write_all_with(buf, range.start, |buf, at| {
+            stats::record_write(self.stats.as_deref(), || self.file.write_at(at, buf))
+        })
+        .map_err(FileRegionError::Io)
+    }
+
+    /// Writes all `N` bytes of `array` at `offset`. Has the same bounds
+    /// semantics as `write()`.
+    pub fn write_array<const N: usize>(
+        &self,
+        offset: u64,
+        array: &[u8; N],
+    ) -> Result<(), FileRegionError> {
+        self.write_full(offset, array)
+    }
+
+    /// Overwrites the whole region with `byte`, writing from a buffer of at
+    /// most `buffer_size()` bytes. To fill only part of the region, call this
+    /// on a `subregion()`. Extends the file if it ends before the region
+    /// does.
     ///
-    /// May return an I/O error from seeking or writing.
-    pub fn write(&mut self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
-        let range = subrange(&self.range, offset..offset + buf.len() as u64)
-            .map_err(FileRegionError::Region)?;
-        self.file
-            .seek(SeekFrom::Start(range.start))
-            .map_err(FileRegionError::Io)?;
-        self.file.write(buf).map_err(FileRegionError::Io)
+    /// May return an I/O error from writing, in which case a prefix of the
+    /// region may have been filled.
+    pub fn fill(&self, byte: u8) -> Result<(), FileRegionError> {
+        let buf = vec![byte; (self.buf_size as u64).min(self.len()) as usize];
+        let mut offset = 0;
+        while offset < self.len() {
+            let n = (self.len() - offset).min(buf.len() as u64) as usize;
+            self.write_full(offset, &buf[..n])?;
+            offset += n as u64;
+        }
+        Ok(())
+    }
+
+    /// Overwrites the whole region with zeros. The same as `fill(0)`.
+    pub fn zero(&self) -> Result<(), FileRegionError> {
+        self.fill(0)
     }
 
-    /// Return a subregion. Checks for some inconsistencies but not all; use
-    /// `is_valid()` to check consistency against the underlying file.
-    pub fn subregion(self, range: Range<u64>) -> Result<FileRegion<'a>, RegionError> {
+    /// Fills the region from the start with bytes from `r`, stopping when the
+    /// region is full or `r` reaches EOF. Never reads more from `r` than the
+    /// region can hold. Returns the number of bytes written.
+    ///
+    /// May return an I/O error from reading or writing.
+    pub fn fill_from_reader(&self, r: &mut impl Read) -> Result<u64, FileRegionError> {
+        let mut buf = vec![0; self.buf_size];
+        let mut offset = 0;
+        while offset < self.len() {
+            let want = (self.len() - offset).min(self.buf_size as u64) as usize;
+            let n = match r.read(&mut buf[..want]) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(FileRegionError::Io(error)),
+            };
+            self.write_full(offset, &buf[..n])?;
+            offset += n as u64;
+        }
+        Ok(offset)
+    }
+
+    /// Fills the region from the start with all of `r`, returning the number
+    /// of bytes copied. Unlike `fill_from_reader()`, which stops quietly when
+    /// the region is full, returns `RegionError::EndOutOfBounds` if `r` still
+    /// has data once the region is full. By then the region has been
+    /// written, and one extra byte has been consumed from `r`.
+    ///
+    /// May return an I/O error from reading or writing.
+    pub fn copy_from_reader(&self, r: &mut impl Read) -> Result<u64, FileRegionError> {
+        let copied = self.fill_from_reader(r)?;
+        if copied == self.len() {
+            let mut probe = [0; 1];
+            loop {
+                match r.read(&mut probe) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        return Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+                            end: copied + 1,
+                            limit: copied,
+                        }))
+                    }
+                    Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(FileRegionError::Io(error)),
+                }
+            }
+        }
+        Ok(copied)
+    }
+
+    /// Like `write()`, but first re-validates the region against the file's
+    /// current length, so writing to a region that a truncation has left
+    /// (partly) past EOF fails instead of silently re-extending the file.
+    /// Performs I/O to get the file's metadata on every call.
+    pub fn checked_write(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        self.validate()?;
+        self.write(offset, buf)
+    }
+}
+
+impl<'a> FileRegion<'a> {
+    /// Creates a new `FileRegion` spanning the entire `file`. Validity is
+    /// guaranteed.
+    pub fn from_file(file: &'a File) -> IoResult<Self> {
+        let range = 0..file.metadata()?.len();
         Ok(FileRegion {
-            file: self.file,
-            range: subrange(&self.range, range)?,
+            file,
+            range,
+            parent_range: None,
+            track_ancestry: false,
+            parent: None,
+            stats: None,
+            pos: 0,
+            buf_size: COPY_BUF_SIZE,
+            oob: OobPolicy::default(),
         })
     }
+
+    /// Returns a `RegionBuilder` for configuring how a region over `file` is
+    /// constructed and validated.
+    pub fn builder(file: &'a File) -> RegionBuilder<'a> {
+        RegionBuilder::new(file)
+    }
+
+    /// Returns the file metadata.
+    pub fn file_metadata(&self) -> IoResult<Metadata> {
+        self.file.metadata()
+    }
+
+    /// Like `read()`, but reads into a possibly uninitialized buffer so
+    /// callers need not zero large buffers first. Returns the initialized
+    /// prefix of `buf` holding the bytes read.
+    ///
+    /// Uses `pread` directly on Unix and WASI; elsewhere, zeroes `buf` and
+    /// reads as `read()` does.
+    pub fn read_uninit<'b>(
+        &self,
+        offset: u64,
+        buf: &'b mut [MaybeUninit<u8>],
+    ) -> Result<&'b mut [u8], FileRegionError> {
+        let len = self.read_len(offset, buf.len())?;
+        let buf = &mut buf[..len];
+        if self.oob == OobPolicy::ZeroFill {
+            buf.fill(MaybeUninit::new(0));
+            // SAFETY: every byte was just initialized.
+            let buf = unsafe { &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8]) };
+            self.read(offset, buf)?;
+            return Ok(buf);
+        }
+        let start = self.range.start + offset;
+        let n = stats::record_read(self.stats.as_deref(), || {
+            positional::read_uninit_at(self.file, buf, start)
+        })
+        .map_err(FileRegionError::Io)?;
+        // SAFETY: `read_uninit_at` initialized the first `n` bytes.
+        Ok(unsafe { &mut *(&mut buf[..n] as *mut [MaybeUninit<u8>] as *mut [u8]) })
+    }
+}
+
+/// Validates the range against the file, as `FileRegion::try_new()` does.
+impl<'a> TryFrom<(&'a File, Range<u64>)> for FileRegion<'a> {
+    type Error = FileRegionError;
+
+    fn try_from((file, range): (&'a File, Range<u64>)) -> Result<Self, Self::Error> {
+        FileRegion::try_new(file, range)
+    }
 }
 
-fn subrange(parent: &Range<u64>, child: Range<u64>) -> Result<Range<u64>, RegionError> {
+/// Spans the entire file, as `FileRegion::from_file()` does. Fallible because
+/// finding the file's length takes I/O.
+impl<'a> TryFrom<&'a File> for FileRegion<'a> {
+    type Error = FileRegionError;
+
+    fn try_from(file: &'a File) -> Result<Self, Self::Error> {
+        Ok(FileRegion::from_file(file)?)
+    }
+}
+
+/// Converts `bounds` to a half-open range, with an unbounded end resolving to
+/// `len`.
+pub(crate) fn resolve_range(
+    bounds: &impl RangeBounds<u64>,
+    len: u64,
+) -> Result<Range<u64>, RegionError> {
+    let start = match bounds.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).ok_or(RegionError::StartOverflow)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match bounds.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).ok_or(RegionError::EndOverflow)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    Ok(start..end)
+}
+
+pub(crate) fn subrange(parent: &Range<u64>, child: Range<u64>) -> Result<Range<u64>, RegionError> {
     let add = |offset: u64| parent.start.checked_add(offset);
     let start = add(child.start).ok_or(RegionError::StartOverflow)?;
     let end = add(child.end).ok_or(RegionError::EndOverflow)?;
-    let range = start..end;
-    validate_range(&range, parent.end)?;
-    Ok(range)
+    validate_range(&child, parent.end.saturating_sub(parent.start))?;
+    Ok(start..end)
 }
 
-/// Validates the range for a provided file length.
-fn validate_range(range: &Range<u64>, len: u64) -> Result<(), RegionError> {
+/// Checks that an access of up to `len` bytes at `offset` starts inside
+/// `parent`, and returns the absolute range of the part of it within
+/// `parent`. The clamping counterpart of `subrange()`, for reads.
+pub(crate) fn clamped_subrange(
+    parent: &Range<u64>,
+    offset: u64,
+    len: usize,
+) -> Result<Range<u64>, RegionError> {
+    let limit = parent.end.saturating_sub(parent.start);
+    let start = parent
+        .start
+        .checked_add(offset)
+        .ok_or(RegionError::StartOverflow)?;
+    if offset >= limit {
+        return Err(RegionError::StartOutOfBounds {
+            start: offset,
+            limit,
+        });
+    }
+    Ok(start..start + (len as u64).min(limit - offset))
+}
+
+/// Validates the range for a provided file length. A range whose start is
+/// after its end is rejected with `StartOutOfBounds`, its end as the limit.
+pub(crate) fn validate_range(range: &Range<u64>, len: u64) -> Result<(), RegionError> {
     // Note the careful usage of `>=` and `>`.
-    if range.start >= len {
-        Err(RegionError::StartOutOfBounds)
+    if range.start > range.end {
+        Err(RegionError::StartOutOfBounds {
+            start: range.start,
+            limit: range.end,
+        })
+    } else if range.start >= len {
+        Err(RegionError::StartOutOfBounds {
+            start: range.start,
+            limit: len,
+        })
     } else if range.end > len {
-        Err(RegionError::EndOutOfBounds)
+        Err(RegionError::EndOutOfBounds {
+            end: range.end,
+            limit: len,
+        })
     } else {
         Ok(())
     }
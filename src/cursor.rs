@@ -0,0 +1,87 @@
+use std::fs::File;
+use std::io::{Read, Result as IoResult, Seek, SeekFrom, Write};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+use super::io::seek_within;
+
+/// A read/write position over a borrowed region. Any number of cursors can
+/// share one region, each moving independently, which suits parsing several
+/// parts of a region at once. Implements `Read`, `Write`, and `Seek` with the
+/// same clamping semantics as `FileRegion`'s own impls.
+pub struct RegionCursor<'r, 'a, B: ?Sized = File> {
+    region: &'r FileRegion<'a, B>,
+    pos: u64,
+}
+
+impl<'a, B: RegionBackend + ?Sized> FileRegion<'a, B> {
+    /// Returns a new cursor over this region, positioned at its start.
+    pub fn cursor(&self) -> RegionCursor<'_, 'a, B> {
+        RegionCursor::new(self)
+    }
+}
+
+impl<'r, 'a, B: RegionBackend + ?Sized> RegionCursor<'r, 'a, B> {
+    /// Creates a new cursor positioned at the start of `region`.
+    pub fn new(region: &'r FileRegion<'a, B>) -> RegionCursor<'r, 'a, B> {
+        RegionCursor { region, pos: 0 }
+    }
+
+    /// Returns the region.
+    pub fn region(&self) -> &'r FileRegion<'a, B> {
+        self.region
+    }
+
+    /// Returns the position, relative to the start of the region.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position, clamped to the length of the region.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos.min(self.region.len());
+    }
+
+    /// Moves the position back to the start of the region.
+    pub fn rewind(&mut self) {
+        self.pos = 0;
+    }
+
+    /// Moves the position by `delta` bytes, clamped to the region.
+    pub fn advance(&mut self, delta: i64) {
+        self.pos = seek_within(self.region.len(), self.pos, SeekFrom::Current(delta));
+    }
+
+    /// Returns the number of bytes between the position and the end of the
+    /// region.
+    pub fn remaining(&self) -> u64 {
+        self.region.len() - self.pos
+    }
+}
+
+impl<B: RegionBackend + ?Sized> Read for RegionCursor<'_, '_, B> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.region.stream_read(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<B: WriteBackend + ?Sized> Write for RegionCursor<'_, '_, B> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let n = self.region.stream_write(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+impl<B: RegionBackend + ?Sized> Seek for RegionCursor<'_, '_, B> {
+    fn seek(&mut self, pos: SeekFrom) -> IoResult<u64> {
+        self.pos = seek_within(self.region.len(), self.pos, pos);
+        Ok(self.pos)
+    }
+}
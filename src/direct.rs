@@ -0,0 +1,181 @@
+//! Direct I/O, which bypasses the page cache. The device and filesystem
+//! require transfers to be aligned, so `DirectRegion` checks the file offset,
+//! length, and buffer address of every transfer, and `AlignedBuf` provides
+//! suitably aligned memory.
+
+use std::alloc::{self, Layout};
+use std::fs::{File, OpenOptions};
+use std::io::Result as IoResult;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::ptr::NonNull;
+
+use super::core::{clamped_subrange, subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+use super::positional;
+
+/// Opens `path` with `options` plus the platform's direct I/O flag:
+/// `O_DIRECT` on Linux, `F_NOCACHE` on macOS (set after opening), and
+/// `FILE_FLAG_NO_BUFFERING` on Windows. On other platforms the file is opened
+/// normally.
+///
+/// Some filesystems (such as tmpfs) reject direct I/O when opening the file.
+pub fn open(path: impl AsRef<Path>, options: &mut OpenOptions) -> IoResult<File> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_DIRECT);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+        options.custom_flags(FILE_FLAG_NO_BUFFERING);
+    }
+    let file = options.open(path)?;
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::fd::AsRawFd;
+        // SAFETY: `F_NOCACHE` takes an integer argument and the descriptor
+        // belongs to the live `file`.
+        if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) } == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(file)
+}
+
+/// A zero-initialized heap buffer whose address is a multiple of a given
+/// alignment, for use with `DirectRegion`.
+pub struct AlignedBuf {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+// SAFETY: `AlignedBuf` uniquely owns its allocation, like `Vec<u8>`.
+unsafe impl Send for AlignedBuf {}
+// SAFETY: shared access only hands out `&[u8]`.
+unsafe impl Sync for AlignedBuf {}
+
+impl AlignedBuf {
+    /// Allocates `len` zeroed bytes aligned to `align`.
+    ///
+    /// Panics if `align` is not a power of two, or if `len` is zero or not a
+    /// multiple of `align`.
+    pub fn new(len: usize, align: usize) -> AlignedBuf {
+        assert!(len != 0, "length must be non-zero");
+        assert!(
+            len.is_multiple_of(align),
+            "length must be a multiple of the alignment"
+        );
+        let layout = Layout::from_size_align(len, align).expect("alignment must be a power of two");
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        AlignedBuf { ptr, layout }
+    }
+
+    /// Returns the alignment of the buffer's address.
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+}
+
+impl Deref for AlignedBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the allocation holds `layout.size()` initialized bytes.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+
+impl DerefMut for AlignedBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as in `deref`, and `&mut self` guarantees unique access.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` was allocated with `layout` in `new`.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+/// A region for direct I/O, which rejects transfers that are not aligned to
+/// `align` bytes with `RegionError::Misaligned`. Open the file with
+/// `direct::open()` to actually bypass the page cache.
+pub struct DirectRegion<'a> {
+    region: FileRegion<'a>,
+    align: u64,
+}
+
+impl<'a> DirectRegion<'a> {
+    /// Creates a new `DirectRegion`. `align` is typically the logical block
+    /// size of the device, e.g. 512 or 4096.
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn new(region: FileRegion<'a>, align: u64) -> DirectRegion<'a> {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        DirectRegion { region, align }
+    }
+
+    /// Returns the underlying region.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Returns the required alignment.
+    pub fn align(&self) -> u64 {
+        self.align
+    }
+
+    /// Performs a positional direct read at `offset`. The file offset,
+    /// `buf.len()`, and `buf`'s address must all be aligned.
+    ///
+    /// If the region ends within the buffer, the read is rounded up to the
+    /// next aligned length, so bytes past the region may land in `buf`; the
+    /// returned count never includes them.
+    ///
+    /// Returns an error if the read starts beyond the region, or
+    /// `RegionError::EndOverflow` if rounding up would overflow.
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        let range = clamped_subrange(&self.region.range, offset, usize::MAX)?;
+        let start = range.start;
+        self.check_alignment(start, buf.as_ptr(), buf.len())?;
+        let remaining = self.region.range.end - start;
+        let want = remaining
+            .checked_next_multiple_of(self.align)
+            .ok_or(RegionError::EndOverflow)?
+            .min(buf.len() as u64) as usize;
+        let n = positional::read_at(self.region.file, &mut buf[..want], start)?;
+        Ok(n.min(usize::try_from(remaining).unwrap_or(usize::MAX)))
+    }
+
+    /// Performs a positional direct write of `buf` at `offset`. The file
+    /// offset, `buf.len()`, and `buf`'s address must all be aligned, and the
+    /// whole write must lie within the region.
+    ///
+    /// Returns the number of bytes written, which may be short.
+    pub fn write(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        let range = subrange(&self.region.range, offset..end)?;
+        self.check_alignment(range.start, buf.as_ptr(), buf.len())?;
+        Ok(positional::write_at(self.region.file, buf, range.start)?)
+    }
+
+    fn check_alignment(&self, start: u64, ptr: *const u8, len: usize) -> Result<(), RegionError> {
+        let aligned = start.is_multiple_of(self.align)
+            && (len as u64).is_multiple_of(self.align)
+            && (ptr as u64).is_multiple_of(self.align);
+        if aligned {
+            Ok(())
+        } else {
+            Err(RegionError::Misaligned)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+macro_rules! endian_methods {
+    ($($ty:ty => $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident;)*) => {
+        impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+            $(
+                #[doc = concat!("Reads a little-endian `", stringify!($ty), "` at `offset`.")]
+                ///
+                /// Has the same error semantics as `read_array()`.
+                pub fn $read_le(&self, offset: u64) -> Result<$ty, FileRegionError> {
+                    self.read_array(offset).map(<$ty>::from_le_bytes)
+                }
+
+                #[doc = concat!("Reads a big-endian `", stringify!($ty), "` at `offset`.")]
+                ///
+                /// Has the same error semantics as `read_array()`.
+                pub fn $read_be(&self, offset: u64) -> Result<$ty, FileRegionError> {
+                    self.read_array(offset).map(<$ty>::from_be_bytes)
+                }
+            )*
+        }
+
+        impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+            $(
+                #[doc = concat!("Writes `value` as a little-endian `", stringify!($ty), "` at `offset`.")]
+                ///
+                /// Has the same error semantics as `write_array()`.
+                pub fn $write_le(&self, offset: u64, value: $ty) -> Result<(), FileRegionError> {
+                    self.write_array(offset, &value.to_le_bytes())
+                }
+
+                #[doc = concat!("Writes `value` as a big-endian `", stringify!($ty), "` at `offset`.")]
+                ///
+                /// Has the same error semantics as `write_array()`.
+                pub fn $write_be(&self, offset: u64, value: $ty) -> Result<(), FileRegionError> {
+                    self.write_array(offset, &value.to_be_bytes())
+                }
+            )*
+        }
+    };
+}
+
+endian_methods! {
+    u16 => read_u16_le, read_u16_be, write_u16_le, write_u16_be;
+    u32 => read_u32_le, read_u32_be, write_u32_le, write_u32_be;
+    u64 => read_u64_le, read_u64_be, write_u64_le, write_u64_be;
+    i16 => read_i16_le, read_i16_be, write_i16_le, write_i16_be;
+    i32 => read_i32_le, read_i32_be, write_i32_le, write_i32_be;
+    i64 => read_i64_le, read_i64_be, write_i64_le, write_i64_be;
+}
@@ -1,19 +1,165 @@
-use std::io::Error as IoError;
+use std::error::Error;
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::string::FromUtf8Error;
 
 #[derive(Debug)]
 pub enum FileRegionError {
     Io(IoError),
     Region(RegionError),
+    Integrity(IntegrityError),
+    ShortRead(ShortReadError),
+    Utf8(FromUtf8Error),
 }
 
-#[derive(Debug)]
+/// An invalid range or offset.
+///
+/// The out-of-bounds variants carry the offending offset and the limit it
+/// exceeded, relative to whatever was checked against: the region for reads,
+/// writes, and subregions, or the file for validation (where offsets are
+/// absolute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RegionError {
     StartOverflow,
     EndOverflow,
-    StartOutOfBounds,
-    EndOutOfBounds,
+    /// `start` is at or past `limit`.
+    StartOutOfBounds {
+        start: u64,
+        limit: u64,
+    },
+    /// `end` is past `limit`.
+    EndOutOfBounds {
+        end: u64,
+        limit: u64,
+    },
+    LengthMismatch,
+    Misaligned,
+    Overlapping,
+    Disjoint,
+}
+
+/// The category of a `FileRegionError`, returned by `FileRegionError::kind()`.
+/// Unlike the error itself it is `Copy` and `Eq`, so it can be compared and
+/// matched on directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Io(IoErrorKind),
+    Region(RegionError),
+    Integrity,
+    ShortRead,
+    Utf8,
+}
+
+/// A checksum mismatch: the data no longer matches the checksum stored for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegrityError {
+    pub expected: u32,
+    pub actual: u32,
+}
+
+/// An exact read that stopped early because the file ended inside the
+/// region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortReadError {
+    pub requested: usize,
+    pub read: usize,
+}
+
+impl FileRegionError {
+    /// Returns the category of the error. For I/O errors this includes the
+    /// `io::ErrorKind`, and for region errors the `RegionError` itself.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            FileRegionError::Io(error) => ErrorKind::Io(error.kind()),
+            FileRegionError::Region(error) => ErrorKind::Region(*error),
+            FileRegionError::Integrity(_) => ErrorKind::Integrity,
+            FileRegionError::ShortRead(_) => ErrorKind::ShortRead,
+            FileRegionError::Utf8(_) => ErrorKind::Utf8,
+        }
+    }
+
+    /// Returns true for `RegionError::StartOutOfBounds` and
+    /// `RegionError::EndOutOfBounds`.
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(
+            self,
+            FileRegionError::Region(
+                RegionError::StartOutOfBounds { .. } | RegionError::EndOutOfBounds { .. }
+            )
+        )
+    }
+}
+
+impl fmt::Display for FileRegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileRegionError::Io(error) => write!(f, "I/O error: {error}"),
+            FileRegionError::Region(error) => write!(f, "region error: {error}"),
+            FileRegionError::Integrity(error) => error.fmt(f),
+            FileRegionError::ShortRead(error) => error.fmt(f),
+            FileRegionError::Utf8(error) => write!(f, "region is not valid UTF-8: {error}"),
+        }
+    }
+}
+
+/// The source is the wrapped error for the `Io`, `Region`, and `Utf8`
+/// variants. Integrity and short-read errors are complete in themselves.
+impl Error for FileRegionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FileRegionError::Io(error) => Some(error),
+            FileRegionError::Region(error) => Some(error),
+            FileRegionError::Integrity(_) | FileRegionError::ShortRead(_) => None,
+            FileRegionError::Utf8(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::StartOverflow => f.write_str("start offset overflows u64"),
+            RegionError::EndOverflow => f.write_str("end offset overflows u64"),
+            RegionError::StartOutOfBounds { start, limit } => {
+                write!(f, "start {start} is out of bounds (limit {limit})")
+            }
+            RegionError::EndOutOfBounds { end, limit } => {
+                write!(f, "end {end} is out of bounds (limit {limit})")
+            }
+            RegionError::LengthMismatch => f.write_str("lengths do not match"),
+            RegionError::Misaligned => f.write_str("range is not aligned"),
+            RegionError::Overlapping => f.write_str("regions overlap"),
+            RegionError::Disjoint => f.write_str("regions are disjoint"),
+        }
+    }
+}
+
+impl Error for RegionError {}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "checksum mismatch: expected {:#010x}, found {:#010x}",
+            self.expected, self.actual
+        )
+    }
 }
 
+impl Error for IntegrityError {}
+
+impl fmt::Display for ShortReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "short read: requested {} bytes, read {}",
+            self.requested, self.read
+        )
+    }
+}
+
+impl Error for ShortReadError {}
+
 impl From<IoError> for FileRegionError {
     fn from(error: IoError) -> Self {
         FileRegionError::Io(error)
@@ -25,3 +171,20 @@ impl From<RegionError> for FileRegionError {
         FileRegionError::Region(error)
     }
 }
+
+/// Converts to an `io::Error` for callers that speak `io::Result`. I/O errors
+/// are unwrapped. Otherwise the original error becomes the payload (reachable
+/// through `get_ref()` or `into_inner()`), with kind `InvalidInput` for region
+/// errors, `UnexpectedEof` for short reads, and `InvalidData` for integrity
+/// errors and invalid UTF-8.
+impl From<FileRegionError> for IoError {
+    fn from(error: FileRegionError) -> Self {
+        let kind = match error {
+            FileRegionError::Io(error) => return error,
+            FileRegionError::Region(_) => IoErrorKind::InvalidInput,
+            FileRegionError::ShortRead(_) => IoErrorKind::UnexpectedEof,
+            FileRegionError::Integrity(_) | FileRegionError::Utf8(_) => IoErrorKind::InvalidData,
+        };
+        IoError::new(kind, error)
+    }
+}
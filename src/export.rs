@@ -0,0 +1,87 @@
+use std::fs::File;
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+impl FileRegion<'_> {
+    /// Creates (or truncates) the file at `path` and writes the region's bytes
+    /// into it. Returns the number of bytes written.
+    ///
+    /// See `export_to_file()` for details.
+    pub fn export_to_path(&self, path: impl AsRef<Path>) -> Result<u64, FileRegionError> {
+        let dest = File::create(path)?;
+        self.export_to_file(&dest)
+    }
+
+    /// Writes the region's bytes to the start of `dest` and truncates `dest`
+    /// to that length, so `dest` holds exactly the region's contents. Returns
+    /// the number of bytes written, which is less than `len()` only if the
+    /// underlying file ends before the region does.
+    ///
+    /// On Linux this uses `copy_file_range`, letting the kernel (or the
+    /// filesystem) copy without a round trip through user space. Elsewhere,
+    /// or when the kernel cannot copy between the two files, it falls back to
+    /// a buffered copy.
+    pub fn export_to_file(&self, dest: &File) -> Result<u64, FileRegionError> {
+        let copied = match kernel_copy(self, dest)? {
+            Some(copied) => copied,
+            None => {
+                let mut writer = dest;
+                writer.seek(SeekFrom::Start(0))?;
+                self.copy_to_writer(&mut writer)?
+            }
+        };
+        dest.set_len(copied)?;
+        Ok(copied)
+    }
+}
+
+/// Copies with `copy_file_range`. Returns `Ok(None)` if the kernel cannot
+/// copy between these files before any bytes have been copied.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn kernel_copy(region: &FileRegion, dest: &File) -> Result<Option<u64>, FileRegionError> {
+    use std::io::Error as IoError;
+    use std::os::fd::AsRawFd;
+
+    let mut off_in = region.range.start as libc::loff_t;
+    let mut off_out: libc::loff_t = 0;
+    let mut copied = 0;
+    while copied < region.len() {
+        let want = (region.len() - copied).min(isize::MAX as u64) as usize;
+        // SAFETY: both descriptors are borrowed from live `File`s, and the
+        // offset pointers are valid for the duration of the call.
+        let ret = unsafe {
+            libc::copy_file_range(
+                region.file.as_raw_fd(),
+                &mut off_in,
+                dest.as_raw_fd(),
+                &mut off_out,
+                want,
+                0,
+            )
+        };
+        match ret {
+            0 => break,
+            n if n > 0 => copied += n as u64,
+            _ => {
+                let error = IoError::last_os_error();
+                let unsupported = matches!(
+                    error.raw_os_error(),
+                    Some(libc::ENOSYS | libc::EXDEV | libc::EOPNOTSUPP | libc::EINVAL)
+                );
+                if copied == 0 && unsupported {
+                    return Ok(None);
+                }
+                return Err(FileRegionError::Io(error));
+            }
+        }
+    }
+    Ok(Some(copied))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn kernel_copy(_region: &FileRegion, _dest: &File) -> Result<Option<u64>, FileRegionError> {
+    Ok(None)
+}
@@ -0,0 +1,56 @@
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+impl FileRegion<'_> {
+    /// Writes all of `buf` at `offset` like `write_all()`, but first grows the
+    /// file with `set_len` if it ends before the region does. Afterwards the
+    /// whole region exists in the file, and parts not yet written read as
+    /// zeros (on most filesystems without using disk space), so fixed-size
+    /// slots need not be pre-filled. Never shrinks the file.
+    ///
+    /// Checks the bounds of the whole write first, so neither the file nor
+    /// its contents change if any part is out-of-bounds. May return an I/O
+    /// error from reading the file's metadata, resizing, or writing.
+    pub fn write_extend(&self, offset: u64, buf: &[u8]) -> Result<(), FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.range, offset..end)?;
+        if self.file.metadata()?.len() < self.range.end {
+            self.file.set_len(self.range.end)?;
+        }
+        self.write_full(offset, buf)
+    }
+
+    /// Writes all of `buf` at the end of the region and extends the region to
+    /// cover it, growing the file if needed. Returns the new length of the
+    /// region. Turns a region into a growable segment, such as a log
+    /// partition; other regions over the same range, including clones and
+    /// subregions, keep their old bounds.
+    ///
+    /// Returns `RegionError::EndOverflow` if the new end would overflow, and
+    /// `RegionError::StartOutOfBounds` if the (unvalidated) range starts
+    /// after it ends. On any error the region keeps its old bounds, though
+    /// part of `buf` may have been written.
+    pub fn append(&mut self, buf: &[u8]) -> Result<u64, FileRegionError> {
+        if buf.is_empty() {
+            return Ok(self.len());
+        }
+        let old_end = self.range.end;
+        let offset =
+            old_end
+                .checked_sub(self.range.start)
+                .ok_or(RegionError::StartOutOfBounds {
+                    start: self.range.start,
+                    limit: old_end,
+                })?;
+        self.range.end = old_end
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        if let Err(error) = self.write_full(offset, buf) {
+            self.range.end = old_end;
+            return Err(error);
+        }
+        Ok(self.len())
+    }
+}
@@ -0,0 +1,201 @@
+//! A C ABI for regions over raw file descriptors (Unix only, `ffi` feature),
+//! so C and C++ code can reuse the bounds checks. Build a shared library
+//! with `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! A region borrows its descriptor: the caller keeps ownership, must not
+//! close it while any region over it is alive, and frees each region with
+//! `fr_region_free()`. Functions that transfer bytes return the number of
+//! bytes on success or one of the negative `FR_E*` codes on failure.
+//! Lengths above `isize::MAX`, which could not be reported back, are
+//! rejected with `FR_EINVAL` rather than clamped.
+//!
+//! No panic unwinds into C: a function that panics returns `FR_EPANIC`, or
+//! null or 0 if it does not return a code.
+
+use std::ffi::c_int;
+use std::fs::File;
+use std::mem::ManuallyDrop;
+use std::ops::Range;
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+/// The underlying read or write failed, or returned too few bytes.
+pub const FR_EIO: isize = -1;
+/// The access or range lies outside the region or file.
+pub const FR_EBOUNDS: isize = -2;
+/// A null pointer, negative descriptor, or length above `isize::MAX` was
+/// passed.
+pub const FR_EINVAL: isize = -3;
+/// The library panicked; the region should not be used again.
+pub const FR_EPANIC: isize = -4;
+
+/// An opaque region handle for C callers.
+pub struct FrRegion {
+    file: ManuallyDrop<File>,
+    range: Range<u64>,
+}
+
+impl FrRegion {
+    /// Wraps `fd` without taking ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open descriptor that outlives the returned value.
+    unsafe fn borrow(fd: RawFd, range: Range<u64>) -> FrRegion {
+        FrRegion {
+            file: ManuallyDrop::new(File::from_raw_fd(fd)),
+            range,
+        }
+    }
+
+    fn region(&self) -> FileRegion<'_> {
+        FileRegion::new(&self.file, self.range.clone())
+    }
+}
+
+/// Runs `f`, returning `on_panic` instead of unwinding across the C ABI.
+pub(crate) fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+fn error_code(error: FileRegionError) -> isize {
+    match error {
+        FileRegionError::Region(_) => FR_EBOUNDS,
+        _ => FR_EIO,
+    }
+}
+
+/// Creates a region over `start..end` of the file open as `fd`, validating
+/// the range against the file's length. Returns null if `fd` is negative,
+/// the range is invalid, or the length cannot be read.
+///
+/// # Safety
+///
+/// `fd` must be an open descriptor that stays open until the region (and
+/// every subregion of it) has been freed.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_new(fd: c_int, start: u64, end: u64) -> *mut FrRegion {
+    if fd < 0 {
+        return ptr::null_mut();
+    }
+    guard(ptr::null_mut(), || {
+        let region = FrRegion::borrow(fd, start..end);
+        match region.region().validate() {
+            Ok(()) => Box::into_raw(Box::new(region)),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Returns the length of `region` in bytes, or 0 if it is null.
+///
+/// # Safety
+///
+/// `region` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_len(region: *const FrRegion) -> u64 {
+    guard(0, || {
+        region.as_ref().map_or(0, |region| region.region().len())
+    })
+}
+
+/// Reads up to `len` bytes at `offset`, relative to the region, into `buf`,
+/// with the bounds semantics of `FileRegion::read()`.
+///
+/// # Safety
+///
+/// `region` must be null or a live pointer from this module, and `buf` must
+/// be null or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_read(
+    region: *const FrRegion,
+    offset: u64,
+    buf: *mut u8,
+    len: usize,
+) -> isize {
+    let Some(region) = region.as_ref() else {
+        return FR_EINVAL;
+    };
+    if buf.is_null() || len > isize::MAX as usize {
+        return FR_EINVAL;
+    }
+    let buf = std::slice::from_raw_parts_mut(buf, len);
+    guard(FR_EPANIC, || match region.region().read(offset, buf) {
+        Ok(n) => n as isize,
+        Err(error) => error_code(error),
+    })
+}
+
+/// Writes all `len` bytes of `buf` at `offset`, relative to the region,
+/// with the bounds semantics of `FileRegion::write()`: nothing is written if
+/// any part is out-of-bounds.
+///
+/// # Safety
+///
+/// `region` must be null or a live pointer from this module, and `buf` must
+/// be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_write(
+    region: *const FrRegion,
+    offset: u64,
+    buf: *const u8,
+    len: usize,
+) -> isize {
+    let Some(region) = region.as_ref() else {
+        return FR_EINVAL;
+    };
+    if buf.is_null() || len > isize::MAX as usize {
+        return FR_EINVAL;
+    }
+    let buf = std::slice::from_raw_parts(buf, len);
+    guard(FR_EPANIC, || match region.region().write(offset, buf) {
+        Ok(n) => n as isize,
+        Err(error) => error_code(error),
+    })
+}
+
+/// Creates a region over `start..end` relative to `region`, with the bounds
+/// checks of `FileRegion::subregion()`. The new region borrows the same
+/// descriptor and must be freed separately. Returns null if `region` is null
+/// or the range is out-of-bounds.
+///
+/// # Safety
+///
+/// `region` must be null or a live pointer from this module.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_subregion(
+    region: *const FrRegion,
+    start: u64,
+    end: u64,
+) -> *mut FrRegion {
+    let Some(region) = region.as_ref() else {
+        return ptr::null_mut();
+    };
+    guard(ptr::null_mut(), || {
+        match region.region().subregion(start..end) {
+            Ok(sub) => Box::into_raw(Box::new(FrRegion::borrow(
+                region.file.as_raw_fd(),
+                sub.range(),
+            ))),
+            Err(_) => ptr::null_mut(),
+        }
+    })
+}
+
+/// Frees a region without closing its descriptor. Does nothing if `region`
+/// is null.
+///
+/// # Safety
+///
+/// `region` must be null or a live pointer from this module, and must not be
+/// used again.
+#[no_mangle]
+pub unsafe extern "C" fn fr_region_free(region: *mut FrRegion) {
+    if !region.is_null() {
+        guard((), || drop(Box::from_raw(region)));
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::ops::Range;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A region whose end tracks the live length of the file, like `tail -f`
+/// over part of a file. The region starts at a fixed offset and ends at the
+/// current end of the file, optionally capped at a fixed limit.
+pub struct FollowRegion<'a> {
+    file: &'a File,
+    start: u64,
+    limit: Option<u64>,
+    poll_interval: Duration,
+}
+
+impl<'a> FileRegion<'a> {
+    /// Returns a `FollowRegion` starting where this region starts and growing
+    /// with the file up to this region's end.
+    pub fn follow(&self) -> FollowRegion<'a> {
+        FollowRegion::new(self.file, self.range.start).limit(self.range.end)
+    }
+
+    /// Moves the end of the region to the current end of the file, growing
+    /// or shrinking it, and returns the new length. For tailing a file through
+    /// a long-lived `FileRegion`; `FollowRegion` instead refreshes on every
+    /// operation. If the file now ends before the region starts, the region
+    /// becomes empty at its start.
+    ///
+    /// May return an I/O error from reading the file's metadata.
+    pub fn refresh_end(&mut self) -> Result<u64, FileRegionError> {
+        let len = self.file.metadata()?.len();
+        self.range.end = len.max(self.range.start);
+        Ok(self.len())
+    }
+}
+
+impl<'a> FollowRegion<'a> {
+    /// Creates a new `FollowRegion` from `start` to the end of the file, with
+    /// no limit. Performs no I/O.
+    pub fn new(file: &'a File, start: u64) -> FollowRegion<'a> {
+        FollowRegion {
+            file,
+            start,
+            limit: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Caps the end of the region at the absolute file offset `end`.
+    pub fn limit(mut self, end: u64) -> Self {
+        self.limit = Some(end);
+        self
+    }
+
+    /// Sets how often `wait_for_data()` checks the file length. Defaults to
+    /// 100 milliseconds.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Returns the current range: from the start to the current end of the
+    /// file (or the limit, if sooner). Performs I/O to get the file's metadata.
+    pub fn current_range(&self) -> Result<Range<u64>, FileRegionError> {
+        let len = self.file.metadata()?.len();
+        let end = self.limit.map_or(len, |limit| limit.min(len));
+        Ok(self.start..end.max(self.start))
+    }
+
+    /// Returns a `FileRegion` over the data currently available.
+    pub fn region(&self) -> Result<FileRegion<'a>, FileRegionError> {
+        Ok(FileRegion::new(self.file, self.current_range()?))
+    }
+
+    /// Performs a bounded positional read of the data currently available.
+    /// Has the same semantics as `FileRegion::read()` on `region()`.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        self.region()?.read(offset, buf)
+    }
+
+    /// Blocks until the byte at `offset` (relative to the start) is available
+    /// or `timeout` elapses, polling the file length. Returns true if the data
+    /// is available, false on timeout. Waits indefinitely if `timeout` is
+    /// `None`.
+    ///
+    /// Returns `RegionError::StartOutOfBounds` immediately if `offset` is at or
+    /// past the limit, since the data can never arrive.
+    pub fn wait_for_data(
+        &self,
+        offset: u64,
+        timeout: Option<Duration>,
+    ) -> Result<bool, FileRegionError> {
+        let wanted = self
+            .start
+            .checked_add(offset)
+            .ok_or(RegionError::StartOverflow)?;
+        if let Some(limit) = self.limit.filter(|&limit| wanted >= limit) {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: limit.saturating_sub(self.start),
+            }));
+        }
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if self.current_range()?.end > wanted {
+                return Ok(true);
+            }
+            let sleep = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Ok(false);
+                    }
+                    self.poll_interval.min(deadline - now)
+                }
+                None => self.poll_interval,
+            };
+            thread::sleep(sleep);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::Write;
+
+use super::backend::RegionBackend;
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+/// Number of bytes shown on each line of a hex dump.
+const BYTES_PER_LINE: usize = 16;
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Returns a classic offset/hex/ASCII dump of up to `len` bytes starting
+    /// at `offset`, in the style of `hexdump -C`. Offsets are relative to the
+    /// region, and the dump never extends past the end of the region (or the
+    /// end of the file). Intended for debugging; reads the bytes into memory.
+    ///
+    /// Returns an error if `offset` is greater than `len()`.
+    pub fn hexdump(&self, offset: u64, len: usize) -> Result<String, FileRegionError> {
+        let available = self
+            .len()
+            .checked_sub(offset)
+            .ok_or(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len(),
+            })?;
+        let mut buf = vec![0; len.min(usize::try_from(available).unwrap_or(usize::MAX))];
+        let n = if buf.is_empty() {
+            0
+        } else {
+            self.read_full(offset, &mut buf)?
+        };
+        Ok(format_hexdump(offset, &buf[..n]))
+    }
+
+    /// Returns a hex dump of the first `n` bytes of the region. Equivalent to
+    /// `hexdump(0, n)`.
+    pub fn preview(&self, n: usize) -> Result<String, FileRegionError> {
+        self.hexdump(0, n)
+    }
+}
+
+fn format_hexdump(offset: u64, bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(out, "{:08x} ", offset + (i * BYTES_PER_LINE) as u64);
+        for column in 0..BYTES_PER_LINE {
+            if column % 8 == 0 {
+                out.push(' ');
+            }
+            match line.get(column) {
+                Some(byte) => {
+                    let _ = write!(out, "{byte:02x} ");
+                }
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str(" |");
+        out.extend(line.iter().map(|&byte| match byte {
+            0x20..=0x7e => byte as char,
+            _ => '.',
+        }));
+        out.push_str("|\n");
+    }
+    out
+}
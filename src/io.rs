@@ -0,0 +1,94 @@
+use std::io::{BufReader, Error as IoError, Read, Result as IoResult, Seek, SeekFrom, Write};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+
+impl<'a, B: RegionBackend + ?Sized> FileRegion<'a, B> {
+    /// Wraps the region in a `BufReader`, which implements `BufRead` (for
+    /// `read_line()`, `lines()`, `split()`, and so on) while never reading
+    /// past the end of the region. The buffer is `buffer_size()` bytes (8 KiB
+    /// by default), or the region's length if that is smaller. Reading starts
+    /// at the region's cursor.
+    pub fn buf_reader(self) -> BufReader<FileRegion<'a, B>> {
+        let capacity =
+            usize::try_from(self.len()).map_or(self.buf_size, |len| len.min(self.buf_size));
+        BufReader::with_capacity(capacity, self)
+    }
+}
+
+/// Reads from the region's cursor, returning `Ok(0)` at the end of the
+/// region. Uses positional I/O, so the file's own cursor is left alone.
+impl<B: RegionBackend + ?Sized> Read for FileRegion<'_, B> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.stream_read(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+/// Writes at the region's cursor. A write that crosses the end of the region
+/// is truncated to fit, and writing at the end returns `Ok(0)`, which
+/// `write_all` reports as `WriteZero`. Uses positional I/O, so the file's own
+/// cursor is left alone.
+impl<B: WriteBackend + ?Sized> Write for FileRegion<'_, B> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let n = self.stream_write(self.pos, buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Moves the region's cursor. Positions are relative to the region, with
+/// `SeekFrom::End` measured from the end of the region, and are clamped to
+/// `0..=len()` rather than failing or seeking past the end.
+impl<B: RegionBackend + ?Sized> Seek for FileRegion<'_, B> {
+    fn seek(&mut self, pos: SeekFrom) -> IoResult<u64> {
+        self.pos = seek_within(self.len(), self.pos, pos);
+        Ok(self.pos)
+    }
+}
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Reads at `pos` for a stream-style reader, returning `Ok(0)` at the end
+    /// of the region.
+    pub(crate) fn stream_read(&self, pos: u64, buf: &mut [u8]) -> IoResult<usize> {
+        let remaining = self.len().saturating_sub(pos);
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(0);
+        }
+        self.read(pos, &mut buf[..len]).map_err(IoError::from)
+    }
+}
+
+impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+    /// Writes at `pos` for a stream-style writer, truncating the write to the
+    /// region and returning `Ok(0)` at its end.
+    pub(crate) fn stream_write(&self, pos: u64, buf: &[u8]) -> IoResult<usize> {
+        let remaining = self.len().saturating_sub(pos);
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(0);
+        }
+        self.write_at(pos, &buf[..len]).map_err(IoError::from)
+    }
+}
+
+/// Resolves a seek from `current` within a region of `len` bytes, clamping
+/// the result to `0..=len`.
+pub(crate) fn seek_within(len: u64, current: u64, pos: SeekFrom) -> u64 {
+    let (base, delta) = match pos {
+        SeekFrom::Start(offset) => (0, i128::from(offset)),
+        SeekFrom::End(delta) => (len, i128::from(delta)),
+        SeekFrom::Current(delta) => (current, i128::from(delta)),
+    };
+    (i128::from(base) + delta).clamp(0, i128::from(len)) as u64
+}
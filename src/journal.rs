@@ -0,0 +1,137 @@
+use std::fs::File;
+
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+use super::positional;
+use super::verified::crc32;
+
+/// Length of a journal record header: offset and length, each a
+/// little-endian `u64`, then a little-endian CRC-32 of the rest of the
+/// header and the saved bytes.
+const HEADER_LEN: usize = 20;
+
+/// A `FileRegion` wrapper that saves the old contents of every byte range
+/// to a journal file before overwriting it, so in-place updates can be
+/// rolled back, including after a crash.
+///
+/// Each write appends a record of `[offset: u64 LE][len: u64 LE][crc: u32
+/// LE][old bytes]` to the journal and syncs it before touching the region.
+/// `commit()` syncs the region and empties the journal; `undo()` restores the
+/// saved bytes in reverse order and then empties the journal. After a crash,
+/// wrap the same region and journal in a new `JournaledRegion` and call
+/// `undo()`.
+///
+/// A crash while a record is being appended can leave a torn or zero-filled
+/// tail. Replay stops at the first record that is incomplete or whose CRC-32
+/// does not match, so nothing from such a tail is written to the region; the
+/// region write it belonged to had not started.
+pub struct JournaledRegion<'a> {
+    region: FileRegion<'a>,
+    journal: &'a File,
+}
+
+impl<'a> JournaledRegion<'a> {
+    /// Creates a new `JournaledRegion`. Does not touch the journal, so any
+    /// records left from an interrupted session remain available to
+    /// `undo()`.
+    pub fn new(region: FileRegion<'a>, journal: &'a File) -> JournaledRegion<'a> {
+        JournaledRegion { region, journal }
+    }
+
+    /// Returns the underlying region.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Returns true if the journal holds writes that have been neither
+    /// committed nor undone.
+    pub fn is_dirty(&self) -> Result<bool, FileRegionError> {
+        Ok(self.journal.metadata()?.len() > 0)
+    }
+
+    /// Journals the current contents of `offset..offset + buf.len()`, then
+    /// writes `buf` there. Has the same bounds semantics as
+    /// `FileRegion::write()`.
+    ///
+    /// If the file ends inside the range, only the bytes that exist are
+    /// journaled; `undo()` cannot shrink the file back.
+    pub fn write(&mut self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.region.range, offset..end)?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut record = vec![0; HEADER_LEN + buf.len()];
+        let n = self.region.read_full(offset, &mut record[HEADER_LEN..])?;
+        record.truncate(HEADER_LEN + n);
+        record[..8].copy_from_slice(&offset.to_le_bytes());
+        record[8..16].copy_from_slice(&(n as u64).to_le_bytes());
+        let crc = record_crc(&record[..16], &record[HEADER_LEN..]);
+        record[16..HEADER_LEN].copy_from_slice(&crc.to_le_bytes());
+        let end = self.journal.metadata()?.len();
+        positional::write_all_at(self.journal, &record, end)?;
+        self.journal.sync_data()?;
+        self.region.write_full(offset, buf)?;
+        Ok(buf.len())
+    }
+
+    /// Makes the journaled writes permanent: syncs the region's file, then
+    /// empties the journal.
+    pub fn commit(&mut self) -> Result<(), FileRegionError> {
+        self.region.file.sync_data()?;
+        self.clear()
+    }
+
+    /// Restores the bytes saved for every journaled write, newest first, then
+    /// syncs the region's file and empties the journal.
+    ///
+    /// Returns `RegionError::EndOutOfBounds` if a record does not fit in the
+    /// region, which means the journal belongs to a different region.
+    pub fn undo(&mut self) -> Result<(), FileRegionError> {
+        let len = usize::try_from(self.journal.metadata()?.len())
+            .map_err(|_| RegionError::EndOverflow)?;
+        let mut journal = vec![0; len];
+        positional::read_exact_at(self.journal, &mut journal, 0)?;
+        let mut records = Vec::new();
+        let mut rest = &journal[..];
+        while rest.len() >= HEADER_LEN {
+            let offset = u64::from_le_bytes(rest[..8].try_into().unwrap());
+            let len = u64::from_le_bytes(rest[8..16].try_into().unwrap());
+            let crc = u32::from_le_bytes(rest[16..HEADER_LEN].try_into().unwrap());
+            let Some(data) = usize::try_from(len)
+                .ok()
+                .and_then(|len| rest[HEADER_LEN..].get(..len))
+            else {
+                break;
+            };
+            if record_crc(&rest[..16], data) != crc {
+                break;
+            }
+            records.push((offset, data));
+            rest = &rest[HEADER_LEN + data.len()..];
+        }
+        for (offset, data) in records.into_iter().rev() {
+            let end = offset
+                .checked_add(data.len() as u64)
+                .ok_or(RegionError::EndOverflow)?;
+            subrange(&self.region.range, offset..end)?;
+            self.region.write_full(offset, data)?;
+        }
+        self.region.file.sync_data()?;
+        self.clear()
+    }
+
+    fn clear(&mut self) -> Result<(), FileRegionError> {
+        self.journal.set_len(0)?;
+        self.journal.sync_data()?;
+        Ok(())
+    }
+}
+
+/// Returns the CRC-32 stored in a record with the given offset-and-length
+/// `header` and saved bytes `data`.
+fn record_crc(header: &[u8], data: &[u8]) -> u32 {
+    crc32(crc32(!0, header), data) ^ !0
+}
@@ -1,8 +1,95 @@
+mod advise;
+mod allocator;
+mod append;
+mod backend;
+mod buf_writer;
+mod builder;
+mod compare;
+mod copy;
 mod core;
+mod cursor;
+pub mod direct;
+mod endian;
 mod error;
+mod export;
+mod extend;
+mod follow;
+mod hexdump;
+mod io;
+mod journal;
+mod lines;
+mod lock;
+mod oob;
+mod owned;
+mod positional;
+mod prealloc;
+mod records;
+mod reflink;
+mod resize;
+mod search;
+mod set;
+mod shared;
+mod shift;
+mod spec;
+mod staged;
+mod stats;
+mod swap;
+mod transform;
+mod validated;
+mod varint;
+mod vectored;
+mod verified;
 
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+pub mod async_uring;
+#[cfg(feature = "bytes")]
+mod bytes_ext;
+#[cfg(feature = "cap-std")]
+mod cap_std_ext;
+#[cfg(all(feature = "ffi", unix))]
+pub mod ffi;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "zerocopy")]
+mod pod;
+#[cfg(feature = "positioned-io")]
+mod positioned_ext;
+#[cfg(all(feature = "uring", target_os = "linux"))]
+pub mod uring;
+#[cfg(feature = "watch")]
+mod watch;
+
+pub use advise::Advice;
+pub use allocator::RegionAllocator;
+pub use append::AppendRegion;
+pub use backend::{RegionBackend, SeekBackend, WriteBackend};
+pub use buf_writer::RegionBufWriter;
+pub use builder::{RegionBuilder, Validation};
 pub use core::FileRegion;
-pub use error::{FileRegionError, RegionError};
+pub use cursor::RegionCursor;
+pub use error::{ErrorKind, FileRegionError, IntegrityError, RegionError, ShortReadError};
+pub use follow::FollowRegion;
+pub use journal::JournaledRegion;
+pub use lines::LineIndex;
+pub use lock::{RegionLockGuard, RegionLockTable};
+pub use oob::OobPolicy;
+pub use owned::OwnedFileRegion;
+pub use set::RegionSet;
+pub use shared::SharedFileRegion;
+pub use shift::ShiftScope;
+pub use spec::RegionSpec;
+pub use staged::StagedRegion;
+pub use stats::StatsSnapshot;
+pub use transform::{RegionCodec, TransformedRegion};
+pub use validated::ValidatedRegion;
+pub use verified::VerifiedRegion;
+
+#[cfg(any(unix, windows))]
+pub use backend::FileExtBackend;
+#[cfg(feature = "positioned-io")]
+pub use positioned_ext::PositionedBackend;
+#[cfg(feature = "watch")]
+pub use watch::{RegionEvent, RegionWatcher};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,133 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use super::backend::RegionBackend;
+use super::core::{FileRegion, SCAN_BUF_SIZE};
+use super::error::{FileRegionError, RegionError};
+
+/// The start offsets of the lines in a region, for random access by line
+/// number. Build one with `FileRegion::build_line_index()`.
+///
+/// Lines are separated by `\n`, which is not part of any line. As with
+/// `str::lines()`, a final `\n` does not start an extra empty line.
+pub struct LineIndex<'a, B: ?Sized = File> {
+    region: FileRegion<'a, B>,
+    /// Offsets, relative to the region, at which each line starts.
+    starts: Vec<u64>,
+    /// Offset at which the last line's content ends.
+    end: u64,
+}
+
+impl<'a, B: RegionBackend + ?Sized> FileRegion<'a, B> {
+    /// Returns an iterator over the region's lines, read lazily through a
+    /// bounded buffer that never reads past the end of the region. Lines are
+    /// split as in `LineIndex`: on `\n`, which is excluded, with no extra
+    /// empty line after a final `\n`.
+    ///
+    /// Yields `FileRegionError::Utf8` for a line that is not valid UTF-8 and
+    /// carries on with the next line; yields an I/O error from reading and
+    /// then stops.
+    pub fn lines(&self) -> impl Iterator<Item = Result<String, FileRegionError>> + 'a {
+        let mut region = self.clone();
+        region.pos = 0;
+        let mut reader = BufReader::with_capacity(SCAN_BUF_SIZE, region);
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let mut line = Vec::new();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => None,
+                Ok(_) => {
+                    if line.last() == Some(&b'\n') {
+                        line.pop();
+                    }
+                    Some(String::from_utf8(line).map_err(FileRegionError::Utf8))
+                }
+                Err(error) => {
+                    failed = true;
+                    Some(Err(FileRegionError::Io(error)))
+                }
+            }
+        })
+    }
+
+    /// Scans the region once and records where each line starts. Reads
+    /// through a bounded buffer and never reads outside the region.
+    ///
+    /// May return an I/O error from reading.
+    pub fn build_line_index(&self) -> Result<LineIndex<'a, B>, FileRegionError> {
+        let mut starts = Vec::new();
+        let mut end = 0;
+        let mut buf = vec![0; SCAN_BUF_SIZE];
+        let mut offset = 0;
+        while offset < self.len() {
+            let n = self.read_full(offset, &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            if starts.is_empty() {
+                starts.push(0);
+            }
+            for index in memchr::memchr_iter(b'\n', &buf[..n]) {
+                let newline = offset + index as u64;
+                end = newline;
+                starts.push(newline + 1);
+            }
+            offset += n as u64;
+        }
+        // `offset` is where the data ended: the region end, or EOF if sooner.
+        if starts.last() == Some(&offset) {
+            starts.pop();
+        } else {
+            end = offset;
+        }
+        Ok(LineIndex {
+            region: self.with_range(self.range.clone()),
+            starts,
+            end,
+        })
+    }
+}
+
+impl<'a, B: RegionBackend + ?Sized> LineIndex<'a, B> {
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Returns true if the region has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Returns the subregion holding line `n` (zero-based), excluding its
+    /// `\n`. It inherits the indexed region's statistics, buffer size, and
+    /// out-of-bounds policy. Returns `RegionError::StartOutOfBounds`, with line
+    /// numbers in place of offsets, if there is no such line.
+    pub fn line_region(&self, n: usize) -> Result<FileRegion<'a, B>, RegionError> {
+        let start = *self.starts.get(n).ok_or(RegionError::StartOutOfBounds {
+            start: n as u64,
+            limit: self.starts.len() as u64,
+        })?;
+        let end = match self.starts.get(n + 1) {
+            Some(next) => next - 1,
+            None => self.end,
+        };
+        self.region.subregion(start..end)
+    }
+
+    /// Reads line `n` (zero-based) into a `String`, excluding its `\n`.
+    ///
+    /// Returns a region error if there is no such line, or
+    /// `FileRegionError::Utf8` if the line is not valid UTF-8.
+    pub fn line(&self, n: usize) -> Result<String, FileRegionError> {
+        let region = self.line_region(n)?;
+        let mut buf = vec![0; region.len() as usize];
+        if !buf.is_empty() {
+            region.read_exact(0, &mut buf)?;
+        }
+        String::from_utf8(buf).map_err(FileRegionError::Utf8)
+    }
+}
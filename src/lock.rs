@@ -0,0 +1,188 @@
+use std::fs::File;
+use std::ops::Range;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+/// An in-process lock manager for byte ranges of files. Hands out shared and
+/// exclusive guards for regions, so threads writing different extents of the
+/// same file can coordinate without OS locks. Two locks conflict if they are
+/// on the same file, their ranges overlap, and at least one is exclusive.
+/// Empty regions conflict with nothing.
+///
+/// On Unix, files are identified by device and inode, so separately opened
+/// handles to the same file share locks. Elsewhere, they are identified by the
+/// `File` handle itself.
+///
+/// These locks are purely advisory and only coordinate users of the same
+/// table; they do not affect other processes.
+#[derive(Default)]
+pub struct RegionLockTable {
+    held: Mutex<Held>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct Held {
+    next_id: u64,
+    locks: Vec<Lock>,
+}
+
+struct Lock {
+    id: u64,
+    key: FileKey,
+    range: Range<u64>,
+    exclusive: bool,
+}
+
+impl Lock {
+    fn conflicts(&self, key: FileKey, range: &Range<u64>, exclusive: bool) -> bool {
+        (self.exclusive || exclusive)
+            && self.key == key
+            && self.range.start < range.end
+            && range.start < self.range.end
+    }
+}
+
+/// A held lock on a region. The lock is released when the guard is dropped.
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct RegionLockGuard<'t> {
+    table: &'t RegionLockTable,
+    id: u64,
+}
+
+impl Drop for RegionLockGuard<'_> {
+    fn drop(&mut self) {
+        let mut held = self.table.lock_held();
+        held.locks.retain(|lock| lock.id != self.id);
+        drop(held);
+        self.table.released.notify_all();
+    }
+}
+
+impl RegionLockTable {
+    /// Creates an empty lock table.
+    pub fn new() -> RegionLockTable {
+        RegionLockTable::default()
+    }
+
+    /// Acquires a shared lock on `region`, blocking while an overlapping
+    /// exclusive lock is held. May return an I/O error from identifying the
+    /// file.
+    pub fn lock_shared(&self, region: &FileRegion) -> Result<RegionLockGuard<'_>, FileRegionError> {
+        self.lock(region, false)
+    }
+
+    /// Acquires an exclusive lock on `region`, blocking while any overlapping
+    /// lock is held. May return an I/O error from identifying the file.
+    pub fn lock_exclusive(
+        &self,
+        region: &FileRegion,
+    ) -> Result<RegionLockGuard<'_>, FileRegionError> {
+        self.lock(region, true)
+    }
+
+    /// Like `lock_shared()`, but returns `None` instead of blocking.
+    pub fn try_lock_shared(
+        &self,
+        region: &FileRegion,
+    ) -> Result<Option<RegionLockGuard<'_>>, FileRegionError> {
+        self.try_lock(region, false)
+    }
+
+    /// Like `lock_exclusive()`, but returns `None` instead of blocking.
+    pub fn try_lock_exclusive(
+        &self,
+        region: &FileRegion,
+    ) -> Result<Option<RegionLockGuard<'_>>, FileRegionError> {
+        self.try_lock(region, true)
+    }
+
+    fn lock(
+        &self,
+        region: &FileRegion,
+        exclusive: bool,
+    ) -> Result<RegionLockGuard<'_>, FileRegionError> {
+        let key = FileKey::of(region.file)?;
+        let mut held = self.lock_held();
+        while conflicts(&held, key, &region.range, exclusive) {
+            held = self
+                .released
+                .wait(held)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        Ok(self.insert(held, key, region.range.clone(), exclusive))
+    }
+
+    fn try_lock(
+        &self,
+        region: &FileRegion,
+        exclusive: bool,
+    ) -> Result<Option<RegionLockGuard<'_>>, FileRegionError> {
+        let key = FileKey::of(region.file)?;
+        let held = self.lock_held();
+        if conflicts(&held, key, &region.range, exclusive) {
+            return Ok(None);
+        }
+        Ok(Some(self.insert(
+            held,
+            key,
+            region.range.clone(),
+            exclusive,
+        )))
+    }
+
+    fn insert(
+        &self,
+        mut held: MutexGuard<'_, Held>,
+        key: FileKey,
+        range: Range<u64>,
+        exclusive: bool,
+    ) -> RegionLockGuard<'_> {
+        let id = held.next_id;
+        held.next_id += 1;
+        held.locks.push(Lock {
+            id,
+            key,
+            range,
+            exclusive,
+        });
+        RegionLockGuard { table: self, id }
+    }
+
+    fn lock_held(&self) -> MutexGuard<'_, Held> {
+        // The table's state is updated atomically under the mutex, so it is
+        // still consistent if a holder panicked.
+        self.held
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn conflicts(held: &Held, key: FileKey, range: &Range<u64>, exclusive: bool) -> bool {
+    range.start < range.end
+        && held
+            .locks
+            .iter()
+            .any(|lock| lock.conflicts(key, range, exclusive))
+}
+
+/// Identifies a file for locking purposes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FileKey(u64, u64);
+
+impl FileKey {
+    #[cfg(unix)]
+    fn of(file: &File) -> std::io::Result<FileKey> {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = file.metadata()?;
+        Ok(FileKey(metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    fn of(file: &File) -> std::io::Result<FileKey> {
+        Ok(FileKey(0, file as *const File as usize as u64))
+    }
+}
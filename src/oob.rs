@@ -0,0 +1,96 @@
+use std::io::ErrorKind;
+
+use super::backend::RegionBackend;
+use super::core::{clamped_subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+use super::stats;
+
+/// How `read()`, `write()`, `write_at()`, and the operations
+/// built on them treat an access that starts inside the region but extends
+/// past its end. An access that starts at or past the end always fails with
+/// `RegionError::StartOutOfBounds`.
+///
+/// Operations that promise to transfer a whole buffer, such as
+/// `read_exact()` and `write_all()`, fail rather than clamp under every
+/// policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OobPolicy {
+    /// Reads are shortened to the end of the region, and writes fail with
+    /// `RegionError::EndOutOfBounds`. The default.
+    #[default]
+    ClampReads,
+    /// Reads and writes both fail with `RegionError::EndOutOfBounds`, and
+    /// nothing is transferred.
+    Strict,
+    /// Reads and writes are both shortened to the end of the region.
+    Clamp,
+    /// As `ClampReads`, but a read is never short: the part of the region
+    /// past the end of the file reads as zeros, as if the file were sparse.
+    ZeroFill,
+}
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Sets the out-of-bounds policy for this region. Regions derived from it
+    /// inherit the policy.
+    pub fn with_oob_policy(mut self, policy: OobPolicy) -> Self {
+        self.oob = policy;
+        self
+    }
+
+    /// Returns the region's out-of-bounds policy.
+    pub fn oob_policy(&self) -> OobPolicy {
+        self.oob
+    }
+
+    /// Checks that a read of `len` bytes at `offset` starts inside the region,
+    /// and returns how many bytes of it to perform under the policy.
+    pub(crate) fn read_len(&self, offset: u64, len: usize) -> Result<usize, FileRegionError> {
+        let range = clamped_subrange(&self.range, offset, len)?;
+        let clamped = (range.end - range.start) as usize;
+        if self.oob == OobPolicy::Strict && clamped < len {
+            return Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+                end: offset.saturating_add(len as u64),
+                limit: self.len(),
+            }));
+        }
+        Ok(clamped)
+    }
+
+    /// Returns how many bytes of a `len`-byte write at `offset` to attempt
+    /// under the policy. Only `Clamp` shortens writes; the bounds of what is
+    /// left are still checked by the write itself.
+    pub(crate) fn write_len(&self, offset: u64, len: usize) -> usize {
+        if self.oob != OobPolicy::Clamp {
+            return len;
+        }
+        let remaining = self.len().saturating_sub(offset);
+        len.min(usize::try_from(remaining).unwrap_or(usize::MAX))
+    }
+
+    /// Completes a read at `offset` that filled the first `n` bytes of `buf`.
+    /// Under `ZeroFill`, reads on until `buf` is full or the file ends, then
+    /// zeroes the rest, returning `buf.len()`; otherwise returns `n`.
+    pub(crate) fn finish_read(
+        &self,
+        offset: u64,
+        buf: &mut [u8],
+        mut n: usize,
+    ) -> Result<usize, FileRegionError> {
+        if self.oob != OobPolicy::ZeroFill {
+            return Ok(n);
+        }
+        while n < buf.len() {
+            let at = self.range.start + offset + n as u64;
+            match stats::record_read(self.stats.as_deref(), || {
+                self.file.read_at(at, &mut buf[n..])
+            }) {
+                Ok(0) => break,
+                Ok(k) => n += k,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(FileRegionError::Io(error)),
+            }
+        }
+        buf[n..].fill(0);
+        Ok(buf.len())
+    }
+}
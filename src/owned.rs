@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Error as IoError;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+/// A region that owns its `File`, for when the region must outlive the scope
+/// that opened the file. Borrow it as a `FileRegion` with `region()`.
+pub struct OwnedFileRegion {
+    file: File,
+    range: Range<u64>,
+    path: Option<PathBuf>,
+}
+
+impl FileRegion<'_> {
+    /// Opens the file at `path` for reading and writing and returns an owned
+    /// region over it that remembers the path, validating `range` against the
+    /// file as `FileRegion::try_new()` does. Errors from opening the file or
+    /// validating the range include the path; a validation error becomes an
+    /// I/O error of kind `InvalidInput` whose source is the original
+    /// `FileRegionError`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        range: Range<u64>,
+    ) -> Result<OwnedFileRegion, FileRegionError> {
+        FileRegion::open_with(path, range, OpenOptions::new().read(true).write(true))
+    }
+
+    /// Like `open()`, but opens the file read-only.
+    pub fn open_readonly(
+        path: impl AsRef<Path>,
+        range: Range<u64>,
+    ) -> Result<OwnedFileRegion, FileRegionError> {
+        FileRegion::open_with(path, range, OpenOptions::new().read(true))
+    }
+
+    /// Like `open()`, but opens the file with `options`.
+    pub fn open_with(
+        path: impl AsRef<Path>,
+        range: Range<u64>,
+        options: &OpenOptions,
+    ) -> Result<OwnedFileRegion, FileRegionError> {
+        let path = path.as_ref();
+        let owned = OwnedFileRegion::open_with(path, range, options)?;
+        owned
+            .region()
+            .validate()
+            .map_err(|err| with_path(err.into(), path))?;
+        Ok(owned)
+    }
+
+    /// Returns an owned region over the same range with its own duplicate of
+    /// the file handle (`File::try_clone`), so it no longer borrows the
+    /// original `File` and can outlive it or move to another thread.
+    ///
+    /// The duplicate still shares the OS file offset with the original on
+    /// most platforms, so this does not give the region an independent seek
+    /// cursor. It does not need one: every region reads and writes with
+    /// positional I/O, and its `Read`, `Write`, and `Seek` impls track their
+    /// own `FileRegion` cursor, so seeking one region never moves another.
+    /// Code that seeks the `File` directly and needs a separate offset should
+    /// reopen the file by path instead.
+    ///
+    /// May return an I/O error from duplicating the handle.
+    pub fn try_to_owned(&self) -> Result<OwnedFileRegion, FileRegionError> {
+        Ok(OwnedFileRegion::new(
+            self.file.try_clone()?,
+            self.range.clone(),
+        ))
+    }
+}
+
+impl OwnedFileRegion {
+    /// Creates a new `OwnedFileRegion`. As with `FileRegion::new()`, `range` is
+    /// _not_ validated against the `file`.
+    pub fn new(file: File, range: Range<u64>) -> OwnedFileRegion {
+        OwnedFileRegion {
+            file,
+            range,
+            path: None,
+        }
+    }
+
+    /// Creates a new `OwnedFileRegion`, validating the `range` against the
+    /// `file` as with `FileRegion::try_new()`.
+    pub fn try_new(file: File, range: Range<u64>) -> Result<OwnedFileRegion, FileRegionError> {
+        FileRegion::new(&file, range.clone()).validate()?;
+        Ok(OwnedFileRegion::new(file, range))
+    }
+
+    /// Opens the file at `path` with `options` and returns a region over it
+    /// that remembers the path. Does not validate the `range`.
+    pub(crate) fn open_with(
+        path: impl AsRef<Path>,
+        range: Range<u64>,
+        options: &OpenOptions,
+    ) -> Result<OwnedFileRegion, FileRegionError> {
+        let path = path.as_ref();
+        let file = options.open(path).map_err(|err| with_path(err, path))?;
+        Ok(OwnedFileRegion {
+            file,
+            range,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Returns a `FileRegion` borrowing the owned file.
+    pub fn region(&self) -> FileRegion<'_> {
+        FileRegion::new(&self.file, self.range.clone())
+    }
+
+    /// Returns the owned file.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the region (a range).
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns the path the file was opened from, if it was opened by path.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Consumes the region, returning the owned file.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Consumes the region, returning the owned file and the range.
+    pub fn into_parts(self) -> (File, Range<u64>) {
+        (self.file, self.range)
+    }
+}
+
+impl fmt::Debug for OwnedFileRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedFileRegion")
+            .field("path", &self.path)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+/// Returns an error like `err` whose message is prefixed with `path`, keeping
+/// `err` as its source.
+fn with_path(err: IoError, path: &Path) -> IoError {
+    IoError::new(
+        err.kind(),
+        PathError {
+            path: path.to_path_buf(),
+            error: err,
+        },
+    )
+}
+
+/// An I/O error on the file at `path`.
+#[derive(Debug)]
+struct PathError {
+    path: PathBuf,
+    error: IoError,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+/// The source is the error's own payload if it has one, such as the
+/// `FileRegionError` of a failed validation.
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.error.get_ref() {
+            Some(inner) => Some(inner),
+            None => Some(&self.error),
+        }
+    }
+}
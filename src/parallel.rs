@@ -0,0 +1,65 @@
+use rayon::prelude::*;
+
+use super::backend::RegionBackend;
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+impl<'a, B: RegionBackend + Sync + ?Sized> FileRegion<'a, B> {
+    /// Returns a parallel iterator over consecutive subregions of at most
+    /// `size` bytes; only the last may be shorter. Reads and writes on the
+    /// chunks use positional I/O, so they do not contend for the file's
+    /// cursor.
+    ///
+    /// Panics if `size` is zero.
+    pub fn par_chunks(&self, size: u64) -> impl ParallelIterator<Item = FileRegion<'a, B>> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        let len = self.len();
+        let count = len.div_ceil(size);
+        (0..count).into_par_iter().map(move |index| {
+            let start = index * size;
+            let end = (start + size).min(len);
+            self.subregion(start..end)
+                .expect("chunk lies within the region")
+        })
+    }
+
+    /// Calls `f` on each chunk from `par_chunks(size)` using rayon's thread
+    /// pool, returning the results in chunk order. Stops at the first error.
+    ///
+    /// Panics if `size` is zero.
+    pub fn par_process<R, F>(&self, size: u64, f: F) -> Result<Vec<R>, FileRegionError>
+    where
+        F: Fn(FileRegion<'a, B>) -> Result<R, FileRegionError> + Sync + Send,
+        R: Send,
+    {
+        self.par_chunks(size).map(f).collect()
+    }
+
+    /// Reads each chunk from `par_chunks(size)` on rayon's thread pool and
+    /// calls `f` with the chunk's offset (relative to the region) and bytes,
+    /// returning the results in chunk order. Each thread reuses one buffer of
+    /// `size` bytes, so this suits hashing or scanning large files. Stops at
+    /// the first error.
+    ///
+    /// Returns `FileRegionError::ShortRead` if the file ends inside the
+    /// region, or an I/O error from reading.
+    ///
+    /// Panics if `size` is zero or does not fit in `usize`.
+    pub fn par_map_chunks<R, F>(&self, size: u64, f: F) -> Result<Vec<R>, FileRegionError>
+    where
+        F: Fn(u64, &[u8]) -> R + Sync + Send,
+        R: Send,
+    {
+        let buf_len = usize::try_from(size).expect("chunk size must fit in usize");
+        self.par_chunks(size)
+            .map_init(
+                || vec![0; buf_len],
+                |buf, chunk| {
+                    let buf = &mut buf[..chunk.len() as usize];
+                    chunk.read_exact(0, buf)?;
+                    Ok(f(chunk.range.start - self.range.start, buf))
+                },
+            )
+            .collect()
+    }
+}
@@ -0,0 +1,31 @@
+use zerocopy::{FromBytes, Immutable, IntoBytes};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Reads a `T` from the `size_of::<T>()` bytes at `offset`. Suited to
+    /// fixed-layout headers and records that derive `zerocopy::FromBytes`.
+    ///
+    /// Has the same error semantics as `read_array()`.
+    pub fn read_pod<T: FromBytes>(&self, offset: u64) -> Result<T, FileRegionError> {
+        let mut buf = vec![0; size_of::<T>()];
+        self.read_exact(offset, &mut buf)?;
+        // `buf` has exactly the size of `T`, so the conversion cannot fail.
+        Ok(T::read_from_bytes(&buf).expect("buffer has the size of T"))
+    }
+}
+
+impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+    /// Writes the bytes of `value` at `offset`.
+    ///
+    /// Has the same error semantics as `write_array()`.
+    pub fn write_pod<T: IntoBytes + Immutable>(
+        &self,
+        offset: u64,
+        value: &T,
+    ) -> Result<(), FileRegionError> {
+        self.write_full(offset, value.as_bytes())
+    }
+}
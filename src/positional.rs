@@ -0,0 +1,169 @@
+//! Positional I/O on a `&File`. Where the platform supports it, these do not
+//! use or move the file's shared cursor, so they are safe to call from
+//! several threads at once.
+
+use std::fs::File;
+use std::io::{ErrorKind, Result as IoResult};
+use std::mem::MaybeUninit;
+
+use super::core::COPY_BUF_SIZE;
+
+#[cfg(unix)]
+pub(crate) fn read_at(file: &File, buf: &mut [u8], offset: u64) -> IoResult<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+pub(crate) fn write_at(file: &File, buf: &[u8], offset: u64) -> IoResult<usize> {
+    std::os::unix::fs::FileExt::write_at(file, buf, offset)
+}
+
+/// On Windows, `seek_read` moves the cursor but reads at `offset` in a single
+/// call, so concurrent calls do not interfere with each other.
+#[cfg(windows)]
+pub(crate) fn read_at(file: &File, buf: &mut [u8], offset: u64) -> IoResult<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(windows)]
+pub(crate) fn write_at(file: &File, buf: &[u8], offset: u64) -> IoResult<usize> {
+    std::os::windows::fs::FileExt::seek_write(file, buf, offset)
+}
+
+/// Reads into a possibly uninitialized `buf` at `offset` with `pread`, without
+/// zeroing it first. Returns the number of bytes read; exactly that prefix of
+/// `buf` is initialized.
+#[cfg(any(unix, target_os = "wasi"))]
+pub(crate) fn read_uninit_at(
+    file: &File,
+    buf: &mut [MaybeUninit<u8>],
+    offset: u64,
+) -> IoResult<usize> {
+    use std::os::fd::AsRawFd;
+
+    // As in std: Apple platforms reject reads longer than `c_int::MAX`.
+    const READ_LIMIT: usize = if cfg!(target_vendor = "apple") {
+        libc::c_int::MAX as usize - 1
+    } else {
+        libc::ssize_t::MAX as usize
+    };
+    let offset = libc::off_t::try_from(offset).map_err(|_| ErrorKind::InvalidInput)?;
+    let len = buf.len().min(READ_LIMIT);
+    loop {
+        // SAFETY: the descriptor is borrowed from a live `File`, and `pread`
+        // writes at most `len` bytes into `buf`.
+        let n = unsafe { libc::pread(file.as_raw_fd(), buf.as_mut_ptr().cast(), len, offset) };
+        if n >= 0 {
+            return Ok(n as usize);
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+/// Without a raw positional read, zeroes `buf` and falls back to `read_at`.
+#[cfg(not(any(unix, target_os = "wasi")))]
+pub(crate) fn read_uninit_at(
+    file: &File,
+    buf: &mut [MaybeUninit<u8>],
+    offset: u64,
+) -> IoResult<usize> {
+    buf.fill(MaybeUninit::new(0));
+    // SAFETY: every byte was just initialized.
+    let buf = unsafe { &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8]) };
+    read_at(file, buf, offset)
+}
+
+/// WASI has no stable `FileExt`, so call wasi-libc's `pread` directly.
+#[cfg(target_os = "wasi")]
+pub(crate) fn read_at(file: &File, buf: &mut [u8], offset: u64) -> IoResult<usize> {
+    let (ptr, len) = (buf.as_mut_ptr(), buf.len());
+    // SAFETY: a `&mut [u8]` is a valid `[MaybeUninit<u8>]`, and `pread` only
+    // writes initialized bytes into it.
+    let buf = unsafe { std::slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<u8>>(), len) };
+    read_uninit_at(file, buf, offset)
+}
+
+#[cfg(target_os = "wasi")]
+pub(crate) fn write_at(file: &File, buf: &[u8], offset: u64) -> IoResult<usize> {
+    use std::os::fd::AsRawFd;
+
+    let offset = libc::off_t::try_from(offset).map_err(|_| ErrorKind::InvalidInput)?;
+    let len = buf.len().min(libc::ssize_t::MAX as usize);
+    loop {
+        // SAFETY: the descriptor is borrowed from a live `File`, and `pwrite`
+        // reads at most `len` bytes from `buf`.
+        let n = unsafe { libc::pwrite(file.as_raw_fd(), buf.as_ptr().cast(), len, offset) };
+        if n >= 0 {
+            return Ok(n as usize);
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+/// Reads exactly `buf.len()` bytes at `offset`, failing with `UnexpectedEof`
+/// if the file ends first.
+pub(crate) fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> IoResult<()> {
+    while !buf.is_empty() {
+        match read_at(file, buf, offset) {
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+/// Writes all of `buf` at `offset`.
+pub(crate) fn write_all_at(file: &File, buf: &[u8], offset: u64) -> IoResult<()> {
+    write_all_with(buf, offset, |buf, offset| write_at(file, buf, offset))
+}
+
+/// Writes all of `buf` at `offset` by calling `write` (with a suffix of `buf`
+/// and the offset to write it at) until done, retrying interrupted calls.
+/// Fails with `WriteZero` if a call writes nothing.
+pub(crate) fn write_all_with(
+    mut buf: &[u8],
+    mut offset: u64,
+    mut write: impl FnMut(&[u8], u64) -> IoResult<usize>,
+) -> IoResult<()> {
+    while !buf.is_empty() {
+        match write(buf, offset) {
+            Ok(0) => return Err(ErrorKind::WriteZero.into()),
+            Ok(n) => {
+                buf = &buf[n..];
+                offset += n as u64;
+            }
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+/// Copies `len` bytes within `file` from absolute offset `src` to `dst`, with
+/// `memmove` semantics: overlapping ranges are handled by copying in the
+/// direction that never overwrites bytes not yet copied.
+pub(crate) fn move_within(file: &File, src: u64, dst: u64, len: u64) -> IoResult<()> {
+    let mut buf = vec![0; COPY_BUF_SIZE.min(len as usize)];
+    let mut done = 0;
+    while done < len {
+        let n = (len - done).min(buf.len() as u64);
+        // Moving right: copy the last chunk first.
+        let at = if dst > src { len - done - n } else { done };
+        let chunk = &mut buf[..n as usize];
+        read_exact_at(file, chunk, src + at)?;
+        write_all_at(file, chunk, dst + at)?;
+        done += n;
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+
+use positioned_io::{ReadAt, Size, WriteAt};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+
+/// Reads relative to the start of the region. Reading at or past the end of
+/// the region returns `Ok(0)`, so a region behaves like a bounded sub-file.
+impl ReadAt for FileRegion<'_> {
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> IoResult<usize> {
+        if pos >= self.len() {
+            return Ok(0);
+        }
+        FileRegion::read(self, pos, buf).map_err(IoError::from)
+    }
+}
+
+/// Writes relative to the start of the region. A write that crosses the end of
+/// the region is truncated to fit, and writing at or past the end returns
+/// `Ok(0)`, which `write_all_at` reports as `WriteZero`.
+impl WriteAt for FileRegion<'_> {
+    fn write_at(&mut self, pos: u64, buf: &[u8]) -> IoResult<usize> {
+        let Some(remaining) = self.len().checked_sub(pos).filter(|&n| n > 0) else {
+            return Ok(0);
+        };
+        let len = buf
+            .len()
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        FileRegion::write_at(self, pos, &buf[..len]).map_err(IoError::from)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Reports the number of bytes readable from the region: its length, or less
+/// if the file ends before the region does.
+impl Size for FileRegion<'_> {
+    fn size(&self) -> IoResult<Option<u64>> {
+        let file_len = self.file.metadata()?.len();
+        let end = self.range.end.min(file_len);
+        Ok(Some(end.saturating_sub(self.range.start)))
+    }
+}
+
+/// Adapts a `positioned-io` source, such as `positioned_io::RandomAccessFile`
+/// or a `RefCell<Vec<u8>>`, for use as a `RegionBackend`, so a `FileRegion`
+/// can bound it. Reads and writes go through a shared reference to the
+/// source, as `WriteBackend` requires; a source that is only `ReadAt`, such
+/// as a `Vec<u8>`, gives a read-only backend.
+#[derive(Debug)]
+pub struct PositionedBackend<T>(pub T);
+
+/// `len()` fails with `Unsupported` if the source does not know its size.
+impl<T> RegionBackend for PositionedBackend<T>
+where
+    for<'t> &'t T: ReadAt + Size,
+{
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> IoResult<usize> {
+        (&self.0).read_at(offset, buf)
+    }
+
+    fn len(&self) -> IoResult<u64> {
+        (&self.0)
+            .size()?
+            .ok_or_else(|| IoError::new(ErrorKind::Unsupported, "source has no known size"))
+    }
+}
+
+impl<T> WriteBackend for PositionedBackend<T>
+where
+    for<'t> &'t T: ReadAt + WriteAt + Size,
+{
+    fn write_at(&self, offset: u64, buf: &[u8]) -> IoResult<usize> {
+        (&self.0).write_at(offset, buf)
+    }
+}
@@ -0,0 +1,46 @@
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+impl FileRegion<'_> {
+    /// Allocates backing storage for the region's byte range, so that later
+    /// writes within it cannot fail for lack of disk space. Extends the file
+    /// if it ends before the region does; never shrinks it.
+    ///
+    /// Uses `posix_fallocate` on Linux, Android, and FreeBSD. Elsewhere, falls
+    /// back to extending the file with `set_len`, which guarantees the length
+    /// but not the storage (the new range may be sparse).
+    ///
+    /// Returns `RegionError::EndOverflow` if the range does not fit in the
+    /// platform's file offset type, or an I/O error from the allocation.
+    pub fn preallocate(&self) -> Result<(), FileRegionError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        fallocate(self)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+fn fallocate(region: &FileRegion) -> Result<(), FileRegionError> {
+    use std::os::fd::AsRawFd;
+
+    let offset = libc::off_t::try_from(region.range.start).map_err(|_| RegionError::EndOverflow)?;
+    let len = libc::off_t::try_from(region.len()).map_err(|_| RegionError::EndOverflow)?;
+    offset.checked_add(len).ok_or(RegionError::EndOverflow)?;
+    // SAFETY: the descriptor is borrowed from a live `File`.
+    match unsafe { libc::posix_fallocate(region.file.as_raw_fd(), offset, len) } {
+        0 => Ok(()),
+        errno => Err(FileRegionError::Io(std::io::Error::from_raw_os_error(
+            errno,
+        ))),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+fn fallocate(region: &FileRegion) -> Result<(), FileRegionError> {
+    i64::try_from(region.range.end).map_err(|_| RegionError::EndOverflow)?;
+    if region.file.metadata()?.len() < region.range.end {
+        region.file.set_len(region.range.end)?;
+    }
+    Ok(())
+}
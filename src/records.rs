@@ -0,0 +1,121 @@
+use super::backend::RegionBackend;
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+impl<'a, B: RegionBackend + ?Sized> FileRegion<'a, B> {
+    /// Returns an iterator over consecutive subregions of at most
+    /// `chunk_size` bytes, covering the whole region; only the last may be
+    /// shorter. Performs no I/O. An empty region yields no chunks.
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn chunks(
+        &self,
+        chunk_size: u64,
+    ) -> impl DoubleEndedIterator<Item = FileRegion<'a, B>> + '_ {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let len = self.len();
+        (0..len.div_ceil(chunk_size)).map(move |index| {
+            let start = index * chunk_size;
+            let end = (start + chunk_size).min(len);
+            self.child(self.range.start + start..self.range.start + end)
+        })
+    }
+
+    /// Divides the region into `n` consecutive subregions whose lengths differ
+    /// by at most one byte. When the length does not divide evenly, the first
+    /// `len() % n` parts get the extra byte; when `n` exceeds the length, the
+    /// trailing parts are empty. Performs no I/O.
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_into(&self, n: u64) -> Vec<FileRegion<'a, B>> {
+        assert!(n != 0, "number of parts must be non-zero");
+        let (base, extra) = (self.len() / n, self.len() % n);
+        let mut start = self.range.start;
+        (0..n)
+            .map(|index| {
+                let len = base + u64::from(index < extra);
+                let part = self.child(start..start + len);
+                start += len;
+                part
+            })
+            .collect()
+    }
+
+    /// Splits the region at `offset` into two subregions, `0..offset` and
+    /// `offset..len()`, which do not overlap. Since writes take `&self` and
+    /// use positional I/O, each half can be moved to its own thread and
+    /// written concurrently, e.g. to assemble a file piece by piece. Performs
+    /// no I/O.
+    ///
+    /// Returns `RegionError::StartOutOfBounds` if `offset` is greater than
+    /// `len()`.
+    pub fn split_at(
+        &self,
+        offset: u64,
+    ) -> Result<(FileRegion<'a, B>, FileRegion<'a, B>), RegionError> {
+        if offset > self.len() {
+            return Err(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len(),
+            });
+        }
+        let mid = self.range.start + offset;
+        Ok((
+            self.child(self.range.start..mid),
+            self.child(mid..self.range.end),
+        ))
+    }
+
+    /// Returns an iterator over the region's fixed-size records, as
+    /// subregions of exactly `record_size` bytes each. Performs no I/O.
+    ///
+    /// Returns `RegionError::LengthMismatch` if the region's length is not a
+    /// multiple of `record_size`.
+    ///
+    /// Panics if `record_size` is zero.
+    pub fn records(
+        &self,
+        record_size: u64,
+    ) -> Result<impl ExactSizeIterator<Item = FileRegion<'a, B>> + '_, FileRegionError> {
+        let count = record_count(self.len(), record_size)?;
+        Ok((0..count).map(move |index| {
+            let start = index as u64 * record_size;
+            self.subregion(start..start + record_size)
+                .expect("record lies within the region")
+        }))
+    }
+
+    /// Returns an iterator that reads each fixed-size record in turn and
+    /// passes its bytes to `decode`, yielding the results. Reuses one
+    /// internal buffer for every record.
+    ///
+    /// Returns `RegionError::LengthMismatch` if the region's length is not a
+    /// multiple of `record_size`. Each item is `FileRegionError::ShortRead` if
+    /// the file ends before that record does.
+    ///
+    /// Panics if `record_size` is zero.
+    pub fn records_with<'r, T, F>(
+        &'r self,
+        record_size: usize,
+        mut decode: F,
+    ) -> Result<impl Iterator<Item = Result<T, FileRegionError>> + 'r, FileRegionError>
+    where
+        F: FnMut(&[u8]) -> T + 'r,
+    {
+        let count = record_count(self.len(), record_size as u64)?;
+        let mut buf = vec![0; record_size];
+        Ok((0..count).map(move |index| {
+            self.read_exact(index as u64 * record_size as u64, &mut buf)?;
+            Ok(decode(&buf))
+        }))
+    }
+}
+
+fn record_count(len: u64, record_size: u64) -> Result<usize, FileRegionError> {
+    assert!(record_size != 0, "record size must be non-zero");
+    if !len.is_multiple_of(record_size) {
+        return Err(FileRegionError::Region(RegionError::LengthMismatch));
+    }
+    usize::try_from(len / record_size)
+        .map_err(|_| FileRegionError::Region(RegionError::EndOverflow))
+}
@@ -0,0 +1,68 @@
+use std::io::{Error as IoError, ErrorKind};
+
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+impl FileRegion<'_> {
+    /// Clones this region's bytes into `dest` using the filesystem's
+    /// copy-on-write support, so no data is copied until one side is
+    /// modified. Both regions must have the same length.
+    ///
+    /// Uses `FICLONERANGE` on Linux, which works on btrfs and XFS (among
+    /// others). The filesystem usually requires the offsets and length to be
+    /// block-aligned, except that the source may end at its EOF.
+    ///
+    /// Returns an I/O error with kind `ErrorKind::Unsupported` if the platform
+    /// or filesystem cannot reflink, or if the files are on different
+    /// filesystems. Callers who want a fallback should copy instead.
+    pub fn reflink_to(&self, dest: &mut FileRegion) -> Result<(), FileRegionError> {
+        if self.len() != dest.len() {
+            return Err(FileRegionError::Region(RegionError::LengthMismatch));
+        }
+        if self.is_empty() {
+            return Ok(());
+        }
+        clone_range(self, dest).map_err(FileRegionError::Io)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn clone_range(src: &FileRegion, dest: &FileRegion) -> Result<(), IoError> {
+    use std::os::fd::AsRawFd;
+
+    let args = libc::file_clone_range {
+        src_fd: src.file.as_raw_fd() as i64,
+        src_offset: src.range.start,
+        src_length: src.len(),
+        dest_offset: dest.range.start,
+    };
+    // SAFETY: `args` is a valid `file_clone_range` that outlives the call, and
+    // both descriptors are borrowed from live `File`s.
+    let ret = unsafe { libc::ioctl(dest.file.as_raw_fd(), libc::FICLONERANGE, &args) };
+    if ret == 0 {
+        return Ok(());
+    }
+    let error = IoError::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::EOPNOTSUPP | libc::EXDEV | libc::ENOTTY | libc::ENOSYS) => {
+            Err(unsupported(error))
+        }
+        _ => Err(error),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn clone_range(_src: &FileRegion, _dest: &FileRegion) -> Result<(), IoError> {
+    Err(IoError::new(
+        ErrorKind::Unsupported,
+        "reflink of a byte range is not supported on this platform",
+    ))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn unsupported(error: IoError) -> IoError {
+    IoError::new(
+        ErrorKind::Unsupported,
+        format!("reflink is not supported by the filesystem: {error}"),
+    )
+}
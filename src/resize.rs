@@ -0,0 +1,142 @@
+use std::ops::Range;
+
+use super::core::{validate_range, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+impl FileRegion<'_> {
+    /// Shrinks the region to its first `new_len` bytes, leaving the file
+    /// alone. Performs no I/O.
+    ///
+    /// Returns `RegionError::EndOutOfBounds` if `new_len` is greater than
+    /// `len()`.
+    pub fn truncate(&mut self, new_len: u64) -> Result<(), RegionError> {
+        if new_len > self.len() {
+            return Err(RegionError::EndOutOfBounds {
+                end: new_len,
+                limit: self.len(),
+            });
+        }
+        self.range.end = self.range.start + new_len;
+        Ok(())
+    }
+
+    /// Like `truncate()`, but if the region ended exactly at the end of the
+    /// file, also shrinks the file with `set_len` so that it ends where the
+    /// region now does. A region that ends before EOF leaves the file alone,
+    /// so data after it is never lost.
+    ///
+    /// May return an I/O error from reading the file's metadata or resizing,
+    /// in which case the region keeps its old bounds.
+    pub fn truncate_file(&mut self, new_len: u64) -> Result<(), FileRegionError> {
+        let old_end = self.range.end;
+        self.truncate(new_len)?;
+        let result = match self.file.metadata() {
+            Ok(metadata) if metadata.len() == old_end => self.file.set_len(self.range.end),
+            Ok(_) => Ok(()),
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            self.range.end = old_end;
+            return Err(FileRegionError::Io(error));
+        }
+        Ok(())
+    }
+
+    /// Sets the length of the region to `new_len` by moving its end, with
+    /// checked arithmetic. Performs no I/O and does not check the file; use
+    /// `resize_checked()` for that.
+    ///
+    /// Returns `RegionError::EndOverflow` if the new end would overflow.
+    pub fn resize(&mut self, new_len: u64) -> Result<(), RegionError> {
+        self.range.end = self
+            .range
+            .start
+            .checked_add(new_len)
+            .ok_or(RegionError::EndOverflow)?;
+        Ok(())
+    }
+
+    /// Like `resize()`, but validates the new range against the file first,
+    /// leaving the region unchanged if it does not fit (as `validate()`).
+    pub fn resize_checked(&mut self, new_len: u64) -> Result<(), FileRegionError> {
+        let end = self
+            .range
+            .start
+            .checked_add(new_len)
+            .ok_or(RegionError::EndOverflow)?;
+        validate_range(&(self.range.start..end), self.file.metadata()?.len())?;
+        self.range.end = end;
+        Ok(())
+    }
+
+    /// Moves the end of the region `n` bytes later. See `resize()`.
+    pub fn grow_by(&mut self, n: u64) -> Result<(), RegionError> {
+        let new_len = self.len().checked_add(n).ok_or(RegionError::EndOverflow)?;
+        self.resize(new_len)
+    }
+
+    /// Moves the end of the region `n` bytes earlier. Returns
+    /// `RegionError::EndOutOfBounds` if `n` is greater than `len()`.
+    pub fn shrink_by(&mut self, n: u64) -> Result<(), RegionError> {
+        let new_len = self
+            .len()
+            .checked_sub(n)
+            .ok_or(RegionError::EndOutOfBounds {
+                end: n,
+                limit: self.len(),
+            })?;
+        self.resize(new_len)
+    }
+
+    /// Moves both ends of the region by `delta` bytes, keeping its length:
+    /// later in the file if `delta` is positive, earlier if negative. This
+    /// moves the region's bounds, not its bytes; `shift_insert()` and
+    /// `shift_delete()` move bytes. Performs no I/O; use `shift_checked()` to
+    /// also check the file.
+    ///
+    /// Returns `RegionError::StartOverflow` if the start would go below zero,
+    /// or `RegionError::EndOverflow` if the end would overflow. The region is
+    /// unchanged on error.
+    pub fn shift(&mut self, delta: i64) -> Result<(), RegionError> {
+        self.range = self.shifted(delta)?;
+        Ok(())
+    }
+
+    /// Like `shift()`, but validates the moved range against the file first,
+    /// leaving the region unchanged if it does not fit (as `validate()`).
+    pub fn shift_checked(&mut self, delta: i64) -> Result<(), FileRegionError> {
+        let range = self.shifted(delta)?;
+        validate_range(&range, self.file.metadata()?.len())?;
+        self.range = range;
+        Ok(())
+    }
+
+    /// Shrinks the region to fit within the file's current length, for
+    /// recovering after another process truncates the file. Moves the end
+    /// back to EOF if it lies past it, and the start too if the whole region
+    /// does, leaving an empty region at EOF. Returns the absolute range that
+    /// was trimmed off, which is empty if the region already fit.
+    ///
+    /// May return an I/O error from reading the file's metadata.
+    pub fn clamp_to_file(&mut self) -> Result<Range<u64>, FileRegionError> {
+        let len = self.file.metadata()?.len();
+        let old = self.range.clone();
+        self.range.end = old.end.min(len);
+        self.range.start = old.start.min(self.range.end);
+        Ok(old.start.max(self.range.end)..old.end)
+    }
+
+    fn shifted(&self, delta: i64) -> Result<Range<u64>, RegionError> {
+        let start = self
+            .range
+            .start
+            .checked_add_signed(delta)
+            .ok_or(RegionError::StartOverflow)?;
+        let end = self
+            .range
+            .end
+            .checked_add_signed(delta)
+            .ok_or(RegionError::EndOverflow)?;
+        Ok(start..end)
+    }
+}
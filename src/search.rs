@@ -0,0 +1,99 @@
+use memchr::memmem;
+
+use super::backend::RegionBackend;
+use super::core::{FileRegion, SCAN_BUF_SIZE};
+use super::error::{FileRegionError, RegionError};
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Returns the offset of the first occurrence of `needle` at or after
+    /// `offset`, or `None` if there is none. Offsets are relative to the
+    /// region. Scans with a bounded buffer and never reads outside the region.
+    ///
+    /// An empty `needle` matches at `offset`. Returns an error if `offset` is
+    /// greater than `len()`.
+    pub fn find(&self, offset: u64, needle: &[u8]) -> Result<Option<u64>, FileRegionError> {
+        let len = self.len();
+        if offset > len {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: len,
+            }));
+        }
+        if needle.is_empty() {
+            return Ok(Some(offset));
+        }
+        let mut buf = vec![0; scan_buf_size(needle)];
+        let mut pos = offset;
+        let mut kept = 0;
+        while pos + (kept as u64) < len {
+            let n = self.read_full(pos + kept as u64, &mut buf[kept..])?;
+            if n == 0 {
+                break;
+            }
+            let filled = kept + n;
+            if let Some(index) = search(&buf[..filled], needle) {
+                return Ok(Some(pos + index as u64));
+            }
+            // Keep the tail in case a match straddles the next read.
+            kept = (needle.len() - 1).min(filled);
+            buf.copy_within(filled - kept..filled, 0);
+            pos += (filled - kept) as u64;
+        }
+        Ok(None)
+    }
+
+    /// Returns the offset of the last occurrence of `needle` at or after
+    /// `offset`, or `None` if there is none. Offsets are relative to the
+    /// region. Scans backwards from the end of the region with a bounded
+    /// buffer and never reads outside the region.
+    ///
+    /// An empty `needle` matches at `len()`. Returns an error if `offset` is
+    /// greater than `len()`.
+    pub fn rfind(&self, offset: u64, needle: &[u8]) -> Result<Option<u64>, FileRegionError> {
+        let len = self.len();
+        if offset > len {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: len,
+            }));
+        }
+        if needle.is_empty() {
+            return Ok(Some(len));
+        }
+        let mut buf = vec![0; scan_buf_size(needle)];
+        let mut end = len;
+        while end - offset >= needle.len() as u64 {
+            let start = offset.max(end.saturating_sub(buf.len() as u64));
+            let n = self.read_full(start, &mut buf[..(end - start) as usize])?;
+            if let Some(index) = rsearch(&buf[..n], needle) {
+                return Ok(Some(start + index as u64));
+            }
+            if start == offset {
+                break;
+            }
+            // Overlap with the previous window in case a match straddles it.
+            end = start + needle.len() as u64 - 1;
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the size of the scan buffer: at least twice the needle length, so
+/// that each scan makes progress past the overlap.
+fn scan_buf_size(needle: &[u8]) -> usize {
+    SCAN_BUF_SIZE.max(needle.len().saturating_mul(2))
+}
+
+fn search(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle {
+        [byte] => memchr::memchr(*byte, haystack),
+        _ => memmem::find(haystack, needle),
+    }
+}
+
+fn rsearch(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle {
+        [byte] => memchr::memrchr(*byte, haystack),
+        _ => memmem::rfind(haystack, needle),
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::ops::Range;
+
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+/// A logical region made of ordered, non-overlapping ranges of one file.
+///
+/// Logical offsets run through the ranges in order, so offset 0 is the start
+/// of the first range and `len()` is the sum of the range lengths.
+pub struct RegionSet<'a> {
+    file: &'a File,
+    ranges: Vec<Range<u64>>,
+    /// The logical offset at which each range ends.
+    ends: Vec<u64>,
+}
+
+impl<'a> RegionSet<'a> {
+    /// Creates a new `RegionSet`. Returns `RegionError::Overlapping` unless
+    /// the ranges are sorted, non-overlapping, and each has `start <= end`.
+    /// Like `FileRegion::new()`, the ranges are _not_ validated against the
+    /// `file`.
+    pub fn new(file: &'a File, ranges: Vec<Range<u64>>) -> Result<RegionSet<'a>, RegionError> {
+        let mut ends = Vec::with_capacity(ranges.len());
+        let mut len: u64 = 0;
+        let mut prev_end = 0;
+        for range in &ranges {
+            if range.start > range.end || range.start < prev_end {
+                return Err(RegionError::Overlapping);
+            }
+            prev_end = range.end;
+            len = len
+                .checked_add(range.end - range.start)
+                .ok_or(RegionError::EndOverflow)?;
+            ends.push(len);
+        }
+        Ok(RegionSet { file, ranges, ends })
+    }
+
+    /// Returns the total length of the ranges in bytes.
+    pub fn len(&self) -> u64 {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns if the set is empty (zero total length).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the ranges, in order.
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    /// Returns an iterator over the contiguous pieces of the set, one
+    /// `FileRegion` per range.
+    pub fn chunks(&self) -> impl Iterator<Item = FileRegion<'a>> + '_ {
+        self.ranges
+            .iter()
+            .map(|range| FileRegion::new(self.file, range.clone()))
+    }
+
+    /// Performs a bounded read starting at `logical_offset`, continuing across
+    /// range boundaries until `buf` is full or the set ends. Returns the number
+    /// of bytes read, which is less than requested only at the end of the set
+    /// or if the underlying file ends early.
+    ///
+    /// Returns an error if the read starts beyond the set.
+    ///
+    /// May return an I/O error from reading.
+    pub fn read(&self, logical_offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        if logical_offset >= self.len() {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: logical_offset,
+                limit: self.len(),
+            }));
+        }
+        let mut index = self.ends.partition_point(|&end| end <= logical_offset);
+        let mut offset = logical_offset;
+        let mut filled = 0;
+        while filled < buf.len() && index < self.ranges.len() {
+            let range = &self.ranges[index];
+            let range_start = self.ends[index] - (range.end - range.start);
+            if range.is_empty() {
+                index += 1;
+                continue;
+            }
+            let region = FileRegion::new(self.file, range.clone());
+            let n = region.read_full(offset - range_start, &mut buf[filled..])?;
+            filled += n;
+            offset += n as u64;
+            if offset < self.ends[index] {
+                // The underlying file ended inside this range.
+                break;
+            }
+            index += 1;
+        }
+        Ok(filled)
+    }
+}
+
+impl From<RegionSet<'_>> for Vec<Range<u64>> {
+    fn from(set: RegionSet<'_>) -> Self {
+        set.ranges
+    }
+}
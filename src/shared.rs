@@ -0,0 +1,79 @@
+use std::fs::File;
+use std::ops::{Range, RangeBounds};
+use std::sync::Arc;
+
+use super::core::{resolve_range, subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+/// A region over an `Arc<File>`, so it is `'static`, cheap to clone, and can
+/// be handed to other threads or async tasks. Many regions may share one
+/// file; use the positional `read_at()` and `write_at()`, which do not touch
+/// the file's shared cursor. Borrow it as a `FileRegion` with `region()`.
+#[derive(Clone, Debug)]
+pub struct SharedFileRegion {
+    file: Arc<File>,
+    range: Range<u64>,
+}
+
+impl SharedFileRegion {
+    /// Creates a new `SharedFileRegion`. As with `FileRegion::new()`, `range`
+    /// is _not_ validated against the `file`.
+    pub fn new(file: Arc<File>, range: Range<u64>) -> SharedFileRegion {
+        SharedFileRegion { file, range }
+    }
+
+    /// Creates a new `SharedFileRegion`, validating the `range` against the
+    /// `file` as with `FileRegion::try_new()`.
+    pub fn try_new(
+        file: Arc<File>,
+        range: Range<u64>,
+    ) -> Result<SharedFileRegion, FileRegionError> {
+        FileRegion::new(&*file, range.clone()).validate()?;
+        Ok(SharedFileRegion::new(file, range))
+    }
+
+    /// Returns a `FileRegion` borrowing the shared file.
+    pub fn region(&self) -> FileRegion<'_> {
+        FileRegion::new(&self.file, self.range.clone())
+    }
+
+    /// Returns the shared file.
+    pub fn file(&self) -> &Arc<File> {
+        &self.file
+    }
+
+    /// Returns the region (a range).
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns the length of the region in bytes.
+    pub fn len(&self) -> u64 {
+        self.range.end.saturating_sub(self.range.start)
+    }
+
+    /// Returns if the region is empty (zero length).
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns a region over `range`, relative to this region, sharing the
+    /// same file. Has the same bounds checks as `FileRegion::subregion()`.
+    pub fn subregion(&self, range: impl RangeBounds<u64>) -> Result<SharedFileRegion, RegionError> {
+        let range = resolve_range(&range, self.len())?;
+        Ok(SharedFileRegion::new(
+            Arc::clone(&self.file),
+            subrange(&self.range, range)?,
+        ))
+    }
+
+    /// Performs a bounded positional read. See `FileRegion::read()`.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        self.region().read(offset, buf)
+    }
+
+    /// Performs a bounded positional write. See `FileRegion::write_at()`.
+    pub fn write_at(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        self.region().write_at(offset, buf)
+    }
+}
@@ -0,0 +1,102 @@
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+use super::positional::{move_within, write_all_at};
+
+/// How far `shift_insert()` and `shift_delete()` move bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShiftScope {
+    /// Move bytes only within the region. The region and the file keep their
+    /// lengths: an insert drops the bytes shifted past the region's end, and a
+    /// delete zero-fills the region's tail.
+    Region,
+    /// Move everything from the edit point to the end of the file. The region
+    /// and the file grow on insert and shrink on delete.
+    File,
+}
+
+impl FileRegion<'_> {
+    /// Inserts `data` at `offset`, shifting the bytes after it towards the end
+    /// according to `scope`. `offset` may equal `len()` to insert at the end
+    /// (which only makes room with `ShiftScope::File`). Inserting no bytes
+    /// does nothing.
+    ///
+    /// With `ShiftScope::Region`, returns `RegionError::EndOutOfBounds` if
+    /// `data` does not fit between `offset` and the end of the region.
+    ///
+    /// May return an I/O error; the file contents are then unspecified
+    /// between `offset` and the end of the shifted bytes.
+    pub fn shift_insert(
+        &mut self,
+        offset: u64,
+        data: &[u8],
+        scope: ShiftScope,
+    ) -> Result<(), FileRegionError> {
+        let at = self.absolute_offset(offset)?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        let k = data.len() as u64;
+        let end = offset.checked_add(k).ok_or(RegionError::EndOverflow)?;
+        match scope {
+            ShiftScope::Region => {
+                let inserted = subrange(&self.range, offset..end)?;
+                let tail = self.range.end - inserted.end;
+                move_within(self.file, at, inserted.end, tail)?;
+            }
+            ShiftScope::File => {
+                let file_len = self.file.metadata()?.len();
+                let new_end = self
+                    .range
+                    .end
+                    .checked_add(k)
+                    .ok_or(RegionError::EndOverflow)?;
+                let new_file_len = file_len.checked_add(k).ok_or(RegionError::EndOverflow)?;
+                let dst = at.checked_add(k).ok_or(RegionError::EndOverflow)?;
+                let tail = file_len.saturating_sub(at);
+                self.file.set_len(new_file_len)?;
+                move_within(self.file, at, dst, tail)?;
+                self.range.end = new_end;
+            }
+        }
+        write_all_at(self.file, data, at)?;
+        Ok(())
+    }
+
+    /// Deletes `len` bytes at `offset`, shifting the bytes after them towards
+    /// the start according to `scope`.
+    ///
+    /// Returns a region error if the deleted bytes do not all lie within the
+    /// region.
+    ///
+    /// May return an I/O error; the file contents are then unspecified from
+    /// `offset` onwards.
+    pub fn shift_delete(
+        &mut self,
+        offset: u64,
+        len: u64,
+        scope: ShiftScope,
+    ) -> Result<(), FileRegionError> {
+        let end = offset.checked_add(len).ok_or(RegionError::EndOverflow)?;
+        let deleted = subrange(&self.range, offset..end)?;
+        match scope {
+            ShiftScope::Region => {
+                move_within(
+                    self.file,
+                    deleted.end,
+                    deleted.start,
+                    self.range.end - deleted.end,
+                )?;
+                self.child(self.range.end - len..self.range.end).zero()?;
+            }
+            ShiftScope::File => {
+                let file_len = self.file.metadata()?.len();
+                let tail = file_len.saturating_sub(deleted.end);
+                move_within(self.file, deleted.end, deleted.start, tail)?;
+                self.file
+                    .set_len(file_len.saturating_sub(len).max(deleted.start))?;
+                self.range.end -= len;
+            }
+        }
+        Ok(())
+    }
+}
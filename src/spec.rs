@@ -0,0 +1,44 @@
+use std::fs::{File, OpenOptions};
+use std::ops::Range;
+use std::path::PathBuf;
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+use super::owned::OwnedFileRegion;
+
+/// A plain-data description of a region: a path and a range. Unlike
+/// `FileRegion`, it holds no open file, so it can be stored or sent to
+/// another process (with the `serde` feature) and reopened later.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionSpec {
+    pub path: PathBuf,
+    pub range: Range<u64>,
+}
+
+impl RegionSpec {
+    /// Creates a new `RegionSpec`. Performs no I/O.
+    pub fn new(path: impl Into<PathBuf>, range: Range<u64>) -> RegionSpec {
+        RegionSpec {
+            path: path.into(),
+            range,
+        }
+    }
+
+    /// Opens the file read-only and returns a validated region over it.
+    pub fn open(&self) -> Result<OwnedFileRegion, FileRegionError> {
+        self.open_with(OpenOptions::new().read(true))
+    }
+
+    /// Opens the file with `options` and returns a validated region over it.
+    pub fn open_with(&self, options: &OpenOptions) -> Result<OwnedFileRegion, FileRegionError> {
+        FileRegion::open_with(&self.path, self.range.clone(), options)
+    }
+
+    /// Returns a validated region over `file`, which the caller has already
+    /// opened, typically from `path`; the path itself is not checked. Lets
+    /// many specs over one file share a single handle.
+    pub fn bind<'a>(&self, file: &'a File) -> Result<FileRegion<'a>, FileRegionError> {
+        FileRegion::try_new(file, self.range.clone())
+    }
+}
@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+/// A `FileRegion` wrapper that buffers writes in memory until `commit()`.
+///
+/// Reads see the file contents with any staged writes overlaid on top, in the
+/// order they were made. Nothing touches the file until `commit()`;
+/// `rollback()` discards the staged writes.
+pub struct StagedRegion<'a> {
+    region: FileRegion<'a>,
+    writes: Vec<(u64, Vec<u8>)>,
+}
+
+impl<'a> StagedRegion<'a> {
+    /// Creates a new `StagedRegion` with no staged writes.
+    pub fn new(region: FileRegion<'a>) -> StagedRegion<'a> {
+        StagedRegion {
+            region,
+            writes: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying region.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Returns true if there are staged writes that have not been committed.
+    pub fn is_dirty(&self) -> bool {
+        !self.writes.is_empty()
+    }
+
+    /// Performs a bounded read with the bounds semantics of
+    /// `FileRegion::read()`, reading until `buf` is full or the file ends,
+    /// then overlays any staged writes that fall within it. Staged bytes past
+    /// the end of the file are returned too, with any gap before them read as
+    /// zeros, as it will be after `commit()`.
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        let want = self.region.read_len(offset, buf.len())?;
+        let buf = &mut buf[..want];
+        let n = self.region.read_full(offset, buf)?;
+        let n = self.region.finish_read(offset, buf, n)?;
+        let window = offset..offset + want as u64;
+        let staged_end = self
+            .writes
+            .iter()
+            .map(|(start, data)| intersect(&window, &(*start..*start + data.len() as u64)))
+            .filter(|overlap| !overlap.is_empty())
+            .map(|overlap| overlap.end)
+            .max();
+        let end = staged_end.map_or(offset + n as u64, |end| end.max(offset + n as u64));
+        let read = offset..end;
+        buf[n..(end - offset) as usize].fill(0);
+        for (start, data) in &self.writes {
+            let staged = *start..*start + data.len() as u64;
+            let overlap = intersect(&read, &staged);
+            if overlap.is_empty() {
+                continue;
+            }
+            let dst = (overlap.start - read.start) as usize..(overlap.end - read.start) as usize;
+            let src =
+                (overlap.start - staged.start) as usize..(overlap.end - staged.start) as usize;
+            buf[dst].copy_from_slice(&data[src]);
+        }
+        Ok((end - offset) as usize)
+    }
+
+    /// Stages a write of `buf` at `offset`. Has the same bounds semantics as
+    /// `FileRegion::write()`, but performs no I/O.
+    pub fn write(&mut self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.region.range, offset..end)?;
+        self.writes.push((offset, buf.to_vec()));
+        Ok(buf.len())
+    }
+
+    /// Applies all staged writes to the file, in order, then clears them. If
+    /// an I/O error occurs, the writes not yet applied remain staged.
+    pub fn commit(&mut self) -> Result<(), FileRegionError> {
+        while let Some((offset, data)) = self.writes.first() {
+            self.region.write_full(*offset, data)?;
+            self.writes.remove(0);
+        }
+        Ok(())
+    }
+
+    /// Discards all staged writes.
+    pub fn rollback(&mut self) {
+        self.writes.clear();
+    }
+
+    /// Returns the underlying region, discarding any staged writes.
+    pub fn into_inner(self) -> FileRegion<'a> {
+        self.region
+    }
+}
+
+fn intersect(a: &Range<u64>, b: &Range<u64>) -> Range<u64> {
+    a.start.max(b.start)..a.end.min(b.end)
+}
@@ -0,0 +1,102 @@
+use std::io::Result as IoResult;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// I/O counters for an instrumented region. Enable them with
+/// `FileRegion::with_stats()` and read them with `FileRegion::stats()`.
+#[derive(Debug, Default)]
+pub(crate) struct RegionStats {
+    reads: AtomicU64,
+    bytes_read: AtomicU64,
+    read_nanos: AtomicU64,
+    writes: AtomicU64,
+    bytes_written: AtomicU64,
+    write_nanos: AtomicU64,
+}
+
+/// A point-in-time copy of a region's I/O counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// Number of read calls made to the file, including failed ones.
+    pub reads: u64,
+    /// Number of bytes read.
+    pub bytes_read: u64,
+    /// Total time spent in read calls.
+    pub read_time: Duration,
+    /// Number of write calls made to the file, including failed ones.
+    pub writes: u64,
+    /// Number of bytes written.
+    pub bytes_written: u64,
+    /// Total time spent in write calls.
+    pub write_time: Duration,
+}
+
+impl RegionStats {
+    pub(crate) fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            reads: self.reads.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            read_time: Duration::from_nanos(self.read_nanos.load(Ordering::Relaxed)),
+            writes: self.writes.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            write_time: Duration::from_nanos(self.write_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        for counter in [
+            &self.reads,
+            &self.bytes_read,
+            &self.read_nanos,
+            &self.writes,
+            &self.bytes_written,
+            &self.write_nanos,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Runs the read `op`, recording it in `stats` if present.
+pub(crate) fn record_read(
+    stats: Option<&RegionStats>,
+    op: impl FnOnce() -> IoResult<usize>,
+) -> IoResult<usize> {
+    let Some(stats) = stats else {
+        return op();
+    };
+    let started = Instant::now();
+    let result = op();
+    stats.reads.fetch_add(1, Ordering::Relaxed);
+    stats
+        .read_nanos
+        .fetch_add(elapsed_nanos(started), Ordering::Relaxed);
+    if let Ok(n) = result {
+        stats.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    result
+}
+
+/// Runs the write `op`, recording it in `stats` if present.
+pub(crate) fn record_write(
+    stats: Option<&RegionStats>,
+    op: impl FnOnce() -> IoResult<usize>,
+) -> IoResult<usize> {
+    let Some(stats) = stats else {
+        return op();
+    };
+    let started = Instant::now();
+    let result = op();
+    stats.writes.fetch_add(1, Ordering::Relaxed);
+    stats
+        .write_nanos
+        .fetch_add(elapsed_nanos(started), Ordering::Relaxed);
+    if let Ok(n) = result {
+        stats.bytes_written.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    result
+}
+
+fn elapsed_nanos(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX)
+}
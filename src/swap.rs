@@ -0,0 +1,40 @@
+use super::backend::WriteBackend;
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError};
+
+impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+    /// Exchanges the bytes of this region with those of `other`, which may be
+    /// in the same file or a different one. The regions must have the same
+    /// length. Copies through two bounded buffers, one chunk at a time.
+    ///
+    /// Returns `RegionError::LengthMismatch` if the lengths differ, or
+    /// `RegionError::Overlapping` if the regions partially overlap in the same
+    /// backend. Swapping a region with an identical one does nothing.
+    ///
+    /// May return an I/O error from reading or writing (or
+    /// `FileRegionError::ShortRead` if either file ends before its region
+    /// does). An error part way through leaves the earlier chunks swapped.
+    pub fn swap_with(&self, other: &FileRegion<'_, B>) -> Result<(), FileRegionError> {
+        if self.len() != other.len() {
+            return Err(FileRegionError::Region(RegionError::LengthMismatch));
+        }
+        if self.overlaps(other) {
+            if self.range == other.range {
+                return Ok(());
+            }
+            return Err(FileRegionError::Region(RegionError::Overlapping));
+        }
+        let mut ours = vec![0; self.buf_size];
+        let mut theirs = vec![0; self.buf_size];
+        let mut offset = 0;
+        while offset < self.len() {
+            let n = (self.len() - offset).min(self.buf_size as u64) as usize;
+            self.read_exact(offset, &mut ours[..n])?;
+            other.read_exact(offset, &mut theirs[..n])?;
+            self.write_full(offset, &theirs[..n])?;
+            other.write_full(offset, &ours[..n])?;
+            offset += n as u64;
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,14 @@ use std::io::{Read, Seek, SeekFrom, Write};
 
 use tempfile::tempfile;
 
-use crate::{FileRegion, FileRegionError, RegionError};
+use crate::direct::{self, AlignedBuf, DirectRegion};
+use crate::{
+    Advice, AppendRegion, ErrorKind, FileRegion, FileRegionError, FollowRegion, IntegrityError,
+    JournaledRegion, OobPolicy, OwnedFileRegion, RegionAllocator, RegionBackend, RegionBufWriter,
+    RegionCodec, RegionError, RegionLockTable, RegionSet, RegionSpec, SeekBackend,
+    SharedFileRegion, ShiftScope, ShortReadError, StagedRegion, StatsSnapshot, TransformedRegion,
+    ValidatedRegion, Validation, VerifiedRegion,
+};
 
 fn tempfile_len_10() -> File {
     let mut file = tempfile().unwrap();
@@ -11,6 +18,13 @@ fn tempfile_len_10() -> File {
     file
 }
 
+fn contents(mut file: &File) -> Vec<u8> {
+    let mut content = Vec::new();
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_end(&mut content).unwrap();
+    content
+}
+
 #[test]
 fn test_new_invalid() {
     let file = tempfile().unwrap();
@@ -44,7 +58,9 @@ fn test_try_new_start_out_of_bounds() {
     let file = tempfile_len_10();
     assert!(matches!(
         FileRegion::try_new(&file, 10..15),
-        Err(FileRegionError::Region(RegionError::StartOutOfBounds))
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
     ));
 }
 
@@ -53,8 +69,29 @@ fn test_try_new_end_out_of_bounds() {
     let file = tempfile_len_10();
     assert!(matches!(
         FileRegion::try_new(&file, 5..15),
-        Err(FileRegionError::Region(RegionError::EndOutOfBounds))
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_start_after_end_is_rejected() {
+    let file = tempfile_len_10();
+    #[allow(clippy::reversed_empty_ranges)]
+    let inverted = 5..3;
+    assert!(matches!(
+        FileRegion::try_new(&file, inverted.clone()),
+        Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+            start: 5,
+            limit: 3
+        }))
     ));
+    let region = FileRegion::new(&file, inverted.clone());
+    assert!(!region.is_valid().unwrap());
+    assert_eq!(region.len(), 0);
+    assert_eq!(
+        FileRegion::from_file(&file).unwrap().subregion(inverted),
+        Err(RegionError::StartOutOfBounds { start: 5, limit: 3 })
+    );
 }
 
 #[test]
@@ -92,7 +129,7 @@ fn test_new_empty_region() {
 #[test]
 fn test_read_in_region() {
     let file = tempfile_len_10();
-    let mut fr = FileRegion::new(&file, 2..6);
+    let fr = FileRegion::new(&file, 2..6);
     {
         let mut buf = *b"___";
         assert_eq!(fr.read(0, &mut buf).unwrap(), 3);
@@ -108,7 +145,7 @@ fn test_read_in_region() {
 #[test]
 fn test_read_up_to_region_boundary() {
     let file = tempfile_len_10();
-    let mut fr = FileRegion::new(&file, 2..6);
+    let fr = FileRegion::new(&file, 2..6);
     {
         let mut buf = *b"____";
         assert_eq!(fr.read(0, &mut buf).unwrap(), 4);
@@ -124,22 +161,26 @@ fn test_read_up_to_region_boundary() {
 #[test]
 fn test_start_read_offset_beyond_region() {
     let file = tempfile_len_10();
-    let mut fr = FileRegion::new(&file, 3..7);
+    let fr = FileRegion::new(&file, 3..7);
     let mut buf = [0; 2];
     assert!(matches!(
         fr.read(4, &mut buf),
-        Err(FileRegionError::Region(RegionError::StartOutOfBounds))
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
     ));
     assert!(matches!(
         fr.read(5, &mut buf),
-        Err(FileRegionError::Region(RegionError::StartOutOfBounds))
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
     ));
 }
 
 #[test]
 fn test_read_start_overflow() {
     let file = tempfile().unwrap();
-    let mut region = FileRegion::new(&file, (u64::MAX - 10)..u64::MAX);
+    let region = FileRegion::new(&file, (u64::MAX - 10)..u64::MAX);
     let mut buf = [0; 5];
 
     assert!(matches!(
@@ -153,7 +194,7 @@ fn test_write_in_region() {
     let mut file = tempfile().unwrap();
     file.write_all(&[0; 40]).unwrap();
 
-    let mut fr = FileRegion::new(&file, 10..30);
+    let fr = FileRegion::new(&file, 10..30);
     let written = fr.write(0, b"enshittification").unwrap();
     assert_eq!(written, 16);
 
@@ -171,10 +212,10 @@ fn test_write_starting_in_region_but_too_long() {
     let mut file = tempfile().unwrap();
     file.write_all(&[0; 40]).unwrap();
 
-    let mut fr = FileRegion::new(&file, 10..20);
+    let fr = FileRegion::new(&file, 10..20);
     assert!(matches!(
         fr.write(0, b"enshittification"),
-        Err(FileRegionError::Region(RegionError::EndOutOfBounds))
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
     ));
 }
 
@@ -183,10 +224,28 @@ fn test_write_starting_beyond_region_boundary() {
     let mut file = tempfile().unwrap();
     file.write_all(&[0; 40]).unwrap();
 
-    let mut fr = FileRegion::new(&file, 10..20);
+    let fr = FileRegion::new(&file, 10..20);
     assert!(matches!(
         fr.write(10, b"enshittification"),
-        Err(FileRegionError::Region(RegionError::StartOutOfBounds))
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_write_offset_overflow() {
+    let file = tempfile_len_10();
+    let fr = FileRegion::new(&file, 2..8);
+    for result in [fr.write(u64::MAX, b"x"), fr.write_at(u64::MAX, b"x")] {
+        assert!(matches!(
+            result,
+            Err(FileRegionError::Region(RegionError::EndOverflow))
+        ));
+    }
+    assert!(matches!(
+        fr.write_all(u64::MAX, b"x"),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
     ));
 }
 
@@ -221,7 +280,7 @@ fn test_subregion_start_out_of_bounds() {
     let fr = FileRegion::new(&file, 10..20);
     assert!(matches!(
         fr.subregion(11..15),
-        Err(RegionError::StartOutOfBounds)
+        Err(RegionError::StartOutOfBounds { .. })
     ));
 }
 
@@ -231,7 +290,7 @@ fn test_subregion_end_out_of_bounds() {
     let fr = FileRegion::new(&file, 10..20);
     assert!(matches!(
         fr.subregion(0..11),
-        Err(RegionError::EndOutOfBounds)
+        Err(RegionError::EndOutOfBounds { .. })
     ));
 }
 
@@ -240,7 +299,7 @@ fn test_full_example() {
     let mut file = tempfile().unwrap();
     file.write_all(b"Hello, FileRegion.").unwrap();
 
-    let mut region = FileRegion::new(&file, 7..16);
+    let region = FileRegion::new(&file, 7..16);
     let mut buffer = [0; 9];
     region.read(0, &mut buffer).unwrap();
     assert_eq!(&buffer, b"FileRegio");
@@ -252,3 +311,2712 @@ fn test_full_example() {
     file.read_to_string(&mut content).unwrap();
     assert_eq!(content, "Hello, 01234egion.");
 }
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_read_bytes() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    assert_eq!(region.read_bytes(1, 10).unwrap(), &b"345"[..]);
+    for len in [0, 1] {
+        assert!(matches!(
+            region.read_bytes(100, len),
+            Err(FileRegionError::Region(
+                RegionError::StartOutOfBounds { .. }
+            ))
+        ));
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_read_into_appends() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    let mut dst = bytes::BytesMut::from(&b"ab"[..]);
+    assert_eq!(region.read_into(2, &mut dst).unwrap(), 2);
+    assert_eq!(&dst[..], b"ab45");
+    assert!(matches!(
+        region.read_into(4, &mut dst),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+    assert_eq!(&dst[..], b"ab45");
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_write_bytes() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    region
+        .write_bytes(0, &bytes::Bytes::from_static(b"ab"))
+        .unwrap();
+    assert_eq!(contents(&file), b"01ab456789");
+}
+
+#[test]
+fn test_staged_read_overlays_writes() {
+    let file = tempfile_len_10();
+    let mut staged = StagedRegion::new(FileRegion::new(&file, 2..8));
+    staged.write(1, b"ab").unwrap();
+    staged.write(2, b"c").unwrap();
+    let mut buf = [0; 6];
+    assert_eq!(staged.read(0, &mut buf).unwrap(), 6);
+    assert_eq!(&buf, b"2ac567");
+    assert_eq!(contents(&file), b"0123456789");
+}
+
+#[test]
+fn test_staged_read_past_eof() {
+    let file = tempfile_len_10();
+    let mut staged = StagedRegion::new(FileRegion::new(&file, 2..14));
+    staged.write(10, b"ab").unwrap();
+    let mut buf = [0xff; 8];
+    assert_eq!(staged.read(6, &mut buf).unwrap(), 6);
+    assert_eq!(&buf[..6], b"89\0\0ab");
+    staged.commit().unwrap();
+    assert_eq!(contents(&file), b"0123456789\0\0ab");
+}
+
+#[test]
+fn test_staged_commit() {
+    let file = tempfile_len_10();
+    let mut staged = StagedRegion::new(FileRegion::new(&file, 2..8));
+    staged.write(0, b"ab").unwrap();
+    staged.write(4, b"cd").unwrap();
+    assert!(staged.is_dirty());
+    staged.commit().unwrap();
+    assert!(!staged.is_dirty());
+    assert_eq!(contents(&file), b"01ab45cd89");
+}
+
+#[test]
+fn test_staged_rollback() {
+    let file = tempfile_len_10();
+    let mut staged = StagedRegion::new(FileRegion::new(&file, 2..8));
+    staged.write(0, b"ab").unwrap();
+    staged.rollback();
+    staged.commit().unwrap();
+    assert_eq!(contents(&file), b"0123456789");
+}
+
+#[test]
+fn test_staged_write_out_of_bounds() {
+    let file = tempfile_len_10();
+    let mut staged = StagedRegion::new(FileRegion::new(&file, 2..8));
+    assert!(matches!(
+        staged.write(5, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert!(matches!(
+        staged.write(u64::MAX, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+    assert!(!staged.is_dirty());
+}
+
+#[test]
+fn test_copy_to_writer() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 3..8);
+    let mut out = Vec::new();
+    assert_eq!(region.copy_to_writer(&mut out).unwrap(), 5);
+    assert_eq!(out, b"34567");
+}
+
+#[test]
+fn test_copy_to_writer_empty_region() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 3..3);
+    let mut out = Vec::new();
+    assert_eq!(region.copy_to_writer(&mut out).unwrap(), 0);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_fill_from_reader_stops_at_region_end() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    let mut reader = &b"abcdefgh"[..];
+    assert_eq!(region.fill_from_reader(&mut reader).unwrap(), 4);
+    assert_eq!(reader, b"efgh");
+    assert_eq!(contents(&file), b"01abcd6789");
+}
+
+#[test]
+fn test_fill_from_reader_short_source() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    assert_eq!(region.fill_from_reader(&mut &b"ab"[..]).unwrap(), 2);
+    assert_eq!(contents(&file), b"01ab456789");
+}
+
+#[test]
+fn test_reflink_to_length_mismatch() {
+    let file = tempfile_len_10();
+    let other = tempfile_len_10();
+    let region = FileRegion::new(&file, 0..4);
+    let mut dest = FileRegion::new(&other, 0..5);
+    assert!(matches!(
+        region.reflink_to(&mut dest),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+}
+
+#[test]
+fn test_reflink_to_copies_or_reports_unsupported() {
+    let file = tempfile_len_10();
+    let other = tempfile().unwrap();
+    other.set_len(10).unwrap();
+    let region = FileRegion::from_file(&file).unwrap();
+    let mut dest = FileRegion::from_file(&other).unwrap();
+    match region.reflink_to(&mut dest) {
+        Ok(()) => assert_eq!(contents(&other), b"0123456789"),
+        Err(FileRegionError::Io(error)) => {
+            assert_eq!(error.kind(), std::io::ErrorKind::Unsupported)
+        }
+        Err(error) => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[test]
+fn test_subregion_ancestors() {
+    let file = tempfile().unwrap();
+    let outer = FileRegion::new(&file, 100..2100).with_ancestry();
+    let middle = outer.subregion(200..1200).unwrap();
+    let inner = middle.subregion(50..150).unwrap();
+    assert_eq!(inner.absolute_range(), 350..450);
+    assert_eq!(inner.relative_range(), 50..150);
+    let ancestors: Vec<_> = inner.ancestors().cloned().collect();
+    assert_eq!(ancestors, vec![300..1300, 100..2100]);
+
+    let untracked = FileRegion::new(&file, 100..2100)
+        .subregion(200..1200)
+        .unwrap()
+        .subregion(50..150)
+        .unwrap();
+    assert_eq!(untracked.relative_range(), 50..150);
+    assert_eq!(untracked.ancestors().count(), 0);
+}
+
+#[test]
+fn test_relative_range_without_parent() {
+    let file = tempfile().unwrap();
+    let region = FileRegion::new(&file, 10..20);
+    assert_eq!(region.relative_range(), 10..20);
+    assert_eq!(region.ancestors().count(), 0);
+}
+
+#[test]
+fn test_relative_range_outside_parent() {
+    let file = tempfile_len_10();
+    let parent = FileRegion::new(&file, 1..9).with_ancestry();
+    let aligned = parent.subregion(2..4).unwrap().align_to(8).unwrap();
+    assert_eq!(aligned.range(), 0..8);
+    assert_eq!(aligned.relative_range(), 0..8);
+    assert_eq!(aligned.ancestors().count(), 0);
+
+    let outside = FileRegion::new(&file, 9..10);
+    let union = parent.subregion(6..8).unwrap().union(&outside).unwrap();
+    assert_eq!(union.relative_range(), 7..10);
+
+    let inward = parent.subregion(1..7).unwrap().align_inward(4).unwrap();
+    assert_eq!(inward.relative_range(), 3..7);
+    assert_eq!(inward.ancestors().count(), 1);
+}
+
+#[test]
+fn test_absolute_offset() {
+    let file = tempfile().unwrap();
+    let region = FileRegion::new(&file, 100..200).subregion(10..20).unwrap();
+    assert_eq!(region.absolute_offset(0).unwrap(), 110);
+    assert_eq!(region.absolute_offset(10).unwrap(), 120);
+    assert!(matches!(
+        region.absolute_offset(11),
+        Err(RegionError::StartOutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn test_offset_in() {
+    let file = tempfile().unwrap();
+    let other = tempfile().unwrap();
+    let outer = FileRegion::new(&file, 100..200);
+    let inner = FileRegion::new(&file, 100..200)
+        .subregion(10..20)
+        .unwrap()
+        .subregion(5..10)
+        .unwrap();
+    assert_eq!(inner.offset_in(&outer), Some(15));
+    assert_eq!(outer.offset_in(&inner), None);
+    assert_eq!(inner.offset_in(&FileRegion::new(&other, 0..1000)), None);
+}
+
+#[test]
+fn test_read_array() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    assert_eq!(region.read_array::<4>(1).unwrap(), *b"3456");
+    assert!(matches!(
+        region.read_array::<4>(3),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_read_array_past_end_of_file() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 8..12);
+    assert!(matches!(
+        region.read_array::<4>(0),
+        Err(FileRegionError::ShortRead(ShortReadError {
+            requested: 4,
+            read: 2
+        }))
+    ));
+    assert!(matches!(
+        region.read_exact(0, &mut [0; 4]),
+        Err(FileRegionError::ShortRead(ShortReadError {
+            requested: 4,
+            read: 2
+        }))
+    ));
+}
+
+#[test]
+fn test_write_array() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    region.write_array(2, b"ab").unwrap();
+    assert_eq!(contents(&file), b"0123ab6789");
+    assert!(matches!(
+        region.write_array(5, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_builder_defaults_to_whole_file() {
+    let file = tempfile_len_10();
+    let region = FileRegion::builder(&file).build().unwrap();
+    assert_eq!(region.range(), 0..10);
+}
+
+#[test]
+fn test_builder_eager_validation() {
+    let file = tempfile_len_10();
+    assert!(matches!(
+        FileRegion::builder(&file).range(5..15).build(),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_builder_lazy_validation() {
+    let file = tempfile_len_10();
+    let region = FileRegion::builder(&file)
+        .range(5..15)
+        .validation(Validation::Lazy)
+        .build()
+        .unwrap();
+    assert!(!region.is_valid().unwrap());
+}
+
+#[test]
+fn test_builder_clamp() {
+    let file = tempfile_len_10();
+    let region = FileRegion::builder(&file)
+        .range(5..15)
+        .clamp(true)
+        .build()
+        .unwrap();
+    assert_eq!(region.range(), 5..10);
+}
+
+#[test]
+fn test_builder_align() {
+    let file = tempfile().unwrap();
+    file.set_len(64).unwrap();
+    assert!(FileRegion::builder(&file)
+        .range(16..48)
+        .align(16)
+        .build()
+        .is_ok());
+    assert!(matches!(
+        FileRegion::builder(&file).range(16..40).align(16).build(),
+        Err(FileRegionError::Region(RegionError::Misaligned))
+    ));
+}
+
+#[test]
+fn test_builder_buffer_size() {
+    let file = tempfile_len_10();
+    let region = FileRegion::builder(&file).buffer_size(3).build().unwrap();
+    assert_eq!(region.buffer_size(), 3);
+    assert_eq!(region.subregion(2..8).unwrap().buffer_size(), 3);
+    assert_eq!(FileRegion::new(&file, 0..10).buffer_size(), 8 * 1024);
+    let mut out = Vec::new();
+    assert_eq!(region.copy_to_writer(&mut out).unwrap(), 10);
+    assert_eq!(out, b"0123456789");
+
+    // Comparing, swapping, and filling also go through the configured buffer.
+    let (a, b) = (
+        region.subregion(0..4).unwrap(),
+        region.subregion(5..9).unwrap(),
+    );
+    assert_eq!(a.first_mismatch(&a).unwrap(), None);
+    assert_eq!(a.first_mismatch(&b).unwrap(), Some(0));
+    a.swap_with(&b).unwrap();
+    assert_eq!(contents(&file), b"5678401239");
+    region.subregion(1..9).unwrap().fill(b'x').unwrap();
+    assert_eq!(contents(&file), b"5xxxxxxxx9");
+}
+
+#[test]
+fn test_is_aligned() {
+    let file = tempfile().unwrap();
+    assert!(FileRegion::new(&file, 512..4096).is_aligned(512));
+    assert!(!FileRegion::new(&file, 512..4000).is_aligned(512));
+}
+
+#[test]
+fn test_align_to() {
+    let file = tempfile().unwrap();
+    let region = FileRegion::new(&file, 100..1000).align_to(512).unwrap();
+    assert_eq!(region.range(), 0..1024);
+    let region = FileRegion::new(&file, 0..u64::MAX - 1);
+    assert!(matches!(
+        region.align_to(512),
+        Err(RegionError::EndOverflow)
+    ));
+}
+
+#[test]
+fn test_align_inward() {
+    let file = tempfile().unwrap();
+    let region = FileRegion::new(&file, 100..2000).align_inward(512).unwrap();
+    assert_eq!(region.range(), 512..1536);
+    let region = FileRegion::new(&file, 100..500);
+    assert!(matches!(
+        region.align_inward(512),
+        Err(RegionError::Misaligned)
+    ));
+}
+
+#[test]
+fn test_region_set_new_rejects_overlap() {
+    let file = tempfile_len_10();
+    assert!(matches!(
+        RegionSet::new(&file, vec![0..4, 3..6]),
+        Err(RegionError::Overlapping)
+    ));
+    assert!(matches!(
+        RegionSet::new(&file, vec![5..6, 0..2]),
+        Err(RegionError::Overlapping)
+    ));
+}
+
+#[test]
+fn test_region_set_read_across_ranges() {
+    let file = tempfile_len_10();
+    let set = RegionSet::new(&file, vec![1..3, 5..5, 6..9]).unwrap();
+    assert_eq!(set.len(), 5);
+    let mut buf = [0; 5];
+    assert_eq!(set.read(0, &mut buf).unwrap(), 5);
+    assert_eq!(&buf, b"12678");
+    let mut buf = [0; 2];
+    assert_eq!(set.read(3, &mut buf).unwrap(), 2);
+    assert_eq!(&buf, b"78");
+    assert!(matches!(
+        set.read(5, &mut buf),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_region_set_chunks_and_conversion() {
+    let file = tempfile_len_10();
+    let set = RegionSet::new(&file, vec![1..3, 6..9]).unwrap();
+    let chunks: Vec<_> = set.chunks().map(|region| region.range()).collect();
+    assert_eq!(chunks, vec![1..3, 6..9]);
+    assert_eq!(Vec::from(set), vec![1..3, 6..9]);
+}
+
+#[test]
+fn test_overlaps_and_contains_region() {
+    let file = tempfile().unwrap();
+    let other = tempfile().unwrap();
+    let a = FileRegion::new(&file, 10..20);
+    assert!(a.overlaps(&FileRegion::new(&file, 19..30)));
+    assert!(!a.overlaps(&FileRegion::new(&file, 20..30)));
+    assert!(!a.overlaps(&FileRegion::new(&other, 10..20)));
+    assert!(a.contains_region(&FileRegion::new(&file, 10..20)));
+    assert!(a.contains_region(&FileRegion::new(&file, 12..15)));
+    assert!(!a.contains_region(&FileRegion::new(&file, 12..21)));
+}
+
+#[test]
+fn test_intersection() {
+    let file = tempfile().unwrap();
+    let a = FileRegion::new(&file, 10..20);
+    let b = FileRegion::new(&file, 15..30);
+    assert_eq!(a.intersection(&b).unwrap().range(), 15..20);
+    assert!(a.intersection(&FileRegion::new(&file, 20..30)).is_none());
+}
+
+#[test]
+fn test_union() {
+    let file = tempfile().unwrap();
+    let a = FileRegion::new(&file, 10..20);
+    assert_eq!(
+        a.union(&FileRegion::new(&file, 20..30)).unwrap().range(),
+        10..30
+    );
+    assert_eq!(
+        a.union(&FileRegion::new(&file, 5..12)).unwrap().range(),
+        5..20
+    );
+    assert!(matches!(
+        a.union(&FileRegion::new(&file, 21..30)),
+        Err(RegionError::Disjoint)
+    ));
+}
+
+#[test]
+fn test_difference() {
+    let file = tempfile().unwrap();
+    let a = FileRegion::new(&file, 10..20);
+    let ranges = |regions: Vec<FileRegion>| -> Vec<_> {
+        regions.into_iter().map(|region| region.range()).collect()
+    };
+    assert_eq!(
+        ranges(a.difference(&FileRegion::new(&file, 12..15))),
+        vec![10..12, 15..20]
+    );
+    assert_eq!(
+        ranges(a.difference(&FileRegion::new(&file, 5..15))),
+        vec![15..20]
+    );
+    assert_eq!(ranges(a.difference(&FileRegion::new(&file, 0..30))), vec![]);
+    assert_eq!(
+        ranges(a.difference(&FileRegion::new(&file, 20..30))),
+        vec![10..20]
+    );
+}
+
+#[test]
+fn test_subregion_does_not_consume_parent() {
+    let file = tempfile().unwrap();
+    let parent = FileRegion::new(&file, 100..200);
+    let a = parent.subregion(0..10).unwrap();
+    let b = parent.subregion(50..100).unwrap();
+    assert_eq!(a.range(), 100..110);
+    assert_eq!(b.range(), 150..200);
+    assert_eq!(parent.range(), 100..200);
+}
+
+#[test]
+fn test_into_range() {
+    let file = tempfile().unwrap();
+    assert_eq!(FileRegion::new(&file, 3..7).into_range(), 3..7);
+}
+
+#[test]
+fn test_read_write_endian() {
+    let file = tempfile().unwrap();
+    file.set_len(16).unwrap();
+    let region = FileRegion::new(&file, 4..12);
+    region.write_u32_le(0, 0x0102_0304).unwrap();
+    region.write_u16_be(4, 0x0506).unwrap();
+    assert_eq!(&contents(&file)[4..10], &[4, 3, 2, 1, 5, 6]);
+    assert_eq!(region.read_u32_le(0).unwrap(), 0x0102_0304);
+    assert_eq!(region.read_u32_be(0).unwrap(), 0x0403_0201);
+    assert_eq!(region.read_u16_be(4).unwrap(), 0x0506);
+    assert!(matches!(
+        region.read_u64_le(1),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn test_read_write_pod() {
+    let file = tempfile().unwrap();
+    file.set_len(16).unwrap();
+    let region = FileRegion::new(&file, 4..16);
+    region.write_pod(0, &[1u32, 2u32]).unwrap();
+    assert_eq!(region.read_pod::<[u32; 2]>(0).unwrap(), [1, 2]);
+    assert_eq!(region.read_pod::<u32>(4).unwrap(), 2);
+    assert!(matches!(
+        region.read_pod::<[u32; 2]>(8),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_region_spec_open() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"0123456789").unwrap();
+    let owned = RegionSpec::new(file.path(), 2..6).open().unwrap();
+    assert_eq!(owned.range(), 2..6);
+    let mut buf = [0; 4];
+    owned.region().read(0, &mut buf).unwrap();
+    assert_eq!(&buf, b"2345");
+}
+
+#[test]
+fn test_region_spec_open_validates() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"0123456789").unwrap();
+    assert!(matches!(
+        path_region_error(RegionSpec::new(file.path(), 5..15).open().unwrap_err()),
+        Some(RegionError::EndOutOfBounds { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_region_spec_is_serde() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+    assert_serde::<RegionSpec>();
+}
+
+#[test]
+fn test_read_is_positional() {
+    let mut file = tempfile_len_10();
+    file.seek(SeekFrom::Start(1)).unwrap();
+    let region = FileRegion::new(&file, 2..6);
+    let mut buf = [0; 8];
+    assert_eq!(region.read(1, &mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"345");
+    assert_eq!((&file).stream_position().unwrap(), 1);
+    assert!(matches!(
+        region.read(4, &mut buf),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_write_at() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    assert_eq!(region.write_at(2, b"ab").unwrap(), 2);
+    assert_eq!(contents(&file), b"0123ab6789");
+    assert!(matches!(
+        region.write_at(3, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_chunks() {
+    use rayon::prelude::*;
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 1..10);
+    let ranges: Vec<_> = region.par_chunks(4).map(|chunk| chunk.range()).collect();
+    assert_eq!(ranges, vec![1..5, 5..9, 9..10]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_process() {
+    let file = tempfile_len_10();
+    let region = FileRegion::from_file(&file).unwrap();
+    let sums = region
+        .par_process(3, |chunk| {
+            let mut buf = [0; 3];
+            let n = chunk.read(0, &mut buf)?;
+            Ok(buf[..n].iter().map(|&b| (b - b'0') as u32).sum::<u32>())
+        })
+        .unwrap();
+    assert_eq!(sums, vec![3, 12, 21, 9]);
+}
+
+#[test]
+fn test_find() {
+    let file = tempfile().unwrap();
+    (&file).write_all(b"xxabcabcxx").unwrap();
+    let region = FileRegion::new(&file, 1..9);
+    assert_eq!(region.find(0, b"abc").unwrap(), Some(1));
+    assert_eq!(region.find(2, b"abc").unwrap(), Some(4));
+    assert_eq!(region.find(5, b"abc").unwrap(), None);
+    assert_eq!(region.find(0, b"c").unwrap(), Some(3));
+    assert_eq!(region.find(0, b"cx").unwrap(), Some(6));
+    assert_eq!(region.find(0, b"xx").unwrap(), None);
+    assert_eq!(region.find(3, b"").unwrap(), Some(3));
+    assert!(matches!(
+        region.find(9, b"a"),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_rfind() {
+    let file = tempfile().unwrap();
+    (&file).write_all(b"xxabcabcxx").unwrap();
+    let region = FileRegion::new(&file, 1..9);
+    assert_eq!(region.rfind(0, b"abc").unwrap(), Some(4));
+    assert_eq!(region.rfind(5, b"abc").unwrap(), None);
+    assert_eq!(region.rfind(0, b"x").unwrap(), Some(7));
+    assert_eq!(region.rfind(0, b"").unwrap(), Some(8));
+}
+
+#[test]
+fn test_find_across_buffer_boundary() {
+    let file = tempfile().unwrap();
+    let mut data = vec![0u8; 20_000];
+    data[8190..8194].copy_from_slice(b"MAGI");
+    data[11_806..11_810].copy_from_slice(b"WXYZ");
+    data[16_380..16_386].copy_from_slice(b"MAGIC!");
+    (&file).write_all(&data).unwrap();
+    let region = FileRegion::from_file(&file).unwrap();
+    assert_eq!(region.find(0, b"MAGI").unwrap(), Some(8190));
+    assert_eq!(region.find(0, b"MAGIC").unwrap(), Some(16_380));
+    assert_eq!(region.rfind(0, b"MAGI").unwrap(), Some(16_380));
+    assert_eq!(region.rfind(0, b"MAGIC").unwrap(), Some(16_380));
+    assert_eq!(region.rfind(0, b"WXYZ").unwrap(), Some(11_806));
+}
+
+#[test]
+fn test_line_index() {
+    let file = tempfile().unwrap();
+    (&file).write_all(b"skip\nalpha\n\nbeta\ngamma\n").unwrap();
+    let index = FileRegion::new(&file, 5..23).build_line_index().unwrap();
+    assert_eq!(index.len(), 4);
+    assert_eq!(index.line(0).unwrap(), "alpha");
+    assert_eq!(index.line(1).unwrap(), "");
+    assert_eq!(index.line(2).unwrap(), "beta");
+    assert_eq!(index.line(3).unwrap(), "gamma");
+    assert_eq!(index.line_region(2).unwrap().range(), 12..16);
+    assert!(matches!(
+        index.line(4),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_line_region_inherits_settings() {
+    let file = tempfile().unwrap();
+    (&file).write_all(b"ab\ncd\n").unwrap();
+    let region = FileRegion::new(&file, 0..6)
+        .with_oob_policy(OobPolicy::Clamp)
+        .with_stats();
+    let line = region.build_line_index().unwrap().line_region(1).unwrap();
+    assert_eq!(line.range(), 3..5);
+    assert_eq!(line.oob_policy(), OobPolicy::Clamp);
+    let before = region.stats().unwrap().bytes_read;
+    let mut buf = [0; 4];
+    assert_eq!(line.read(0, &mut buf).unwrap(), 2);
+    assert_eq!(region.stats().unwrap().bytes_read, before + 2);
+}
+
+#[test]
+fn test_line_index_without_trailing_newline() {
+    let file = tempfile().unwrap();
+    (&file).write_all(b"one\ntwo\nthree").unwrap();
+    let index = FileRegion::from_file(&file)
+        .unwrap()
+        .build_line_index()
+        .unwrap();
+    assert_eq!(index.len(), 3);
+    assert_eq!(index.line(2).unwrap(), "three");
+}
+
+#[test]
+fn test_line_index_empty_region() {
+    let file = tempfile_len_10();
+    let index = FileRegion::new(&file, 4..4).build_line_index().unwrap();
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_checked_read_after_truncation() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let mut buf = [0; 2];
+    assert_eq!(region.checked_read(0, &mut buf).unwrap(), 2);
+    file.set_len(6).unwrap();
+    assert!(matches!(
+        region.checked_read(0, &mut buf),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_checked_write_after_truncation() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    file.set_len(6).unwrap();
+    assert!(matches!(
+        region.checked_write(0, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert_eq!(contents(&file), b"012345");
+}
+
+#[test]
+fn test_write_returns_full_length() {
+    let file = tempfile().unwrap();
+    file.set_len(100_000).unwrap();
+    let region = FileRegion::from_file(&file).unwrap();
+    let data = vec![7u8; 100_000];
+    assert_eq!(region.write(0, &data).unwrap(), data.len());
+    assert_eq!(contents(&file), data);
+}
+
+#[test]
+fn test_export_to_file() {
+    let file = tempfile_len_10();
+    let dest = tempfile().unwrap();
+    (&dest).write_all(b"previous contents").unwrap();
+    let region = FileRegion::new(&file, 3..7);
+    assert_eq!(region.export_to_file(&dest).unwrap(), 4);
+    assert_eq!(contents(&dest), b"3456");
+}
+
+#[test]
+fn test_export_to_path() {
+    let file = tempfile_len_10();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("member.bin");
+    let region = FileRegion::new(&file, 5..10);
+    assert_eq!(region.export_to_path(&path).unwrap(), 5);
+    assert_eq!(std::fs::read(&path).unwrap(), b"56789");
+}
+
+#[test]
+fn test_swap_with_same_file() {
+    let file = tempfile_len_10();
+    let a = FileRegion::new(&file, 1..4);
+    let b = FileRegion::new(&file, 6..9);
+    a.swap_with(&b).unwrap();
+    assert_eq!(contents(&file), b"0678451239");
+}
+
+#[test]
+fn test_swap_with_different_files() {
+    let file = tempfile_len_10();
+    let other = tempfile().unwrap();
+    (&other).write_all(b"abcdefghij").unwrap();
+    let a = FileRegion::new(&file, 0..5);
+    let b = FileRegion::new(&other, 5..10);
+    a.swap_with(&b).unwrap();
+    assert_eq!(contents(&file), b"fghij56789");
+    assert_eq!(contents(&other), b"abcde01234");
+}
+
+#[test]
+fn test_swap_with_errors() {
+    let file = tempfile_len_10();
+    let a = FileRegion::new(&file, 0..4);
+    assert!(matches!(
+        a.swap_with(&FileRegion::new(&file, 5..8)),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+    assert!(matches!(
+        a.swap_with(&FileRegion::new(&file, 2..6)),
+        Err(FileRegionError::Region(RegionError::Overlapping))
+    ));
+    a.swap_with(&FileRegion::new(&file, 0..4)).unwrap();
+    assert_eq!(contents(&file), b"0123456789");
+}
+
+#[test]
+fn test_shift_insert_region_scope() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.shift_insert(1, b"ab", ShiftScope::Region).unwrap();
+    assert_eq!(contents(&file), b"012ab34589");
+    assert_eq!(region.range(), 2..8);
+    assert!(matches!(
+        region.shift_insert(5, b"ab", ShiftScope::Region),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    region.shift_insert(6, b"", ShiftScope::Region).unwrap();
+    assert_eq!(contents(&file), b"012ab34589");
+    let mut huge = FileRegion::new(&file, 0..u64::MAX);
+    assert!(matches!(
+        huge.shift_insert(u64::MAX, b"x", ShiftScope::Region),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+}
+
+#[test]
+fn test_shift_insert_file_scope() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.shift_insert(1, b"ab", ShiftScope::File).unwrap();
+    assert_eq!(contents(&file), b"012ab3456789");
+    assert_eq!(region.range(), 2..10);
+    region.shift_insert(8, b"!", ShiftScope::File).unwrap();
+    assert_eq!(contents(&file), b"012ab34567!89");
+    assert_eq!(region.range(), 2..11);
+}
+
+#[test]
+fn test_shift_delete_region_scope() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.shift_delete(1, 2, ShiftScope::Region).unwrap();
+    assert_eq!(contents(&file), b"012567\0\089");
+    assert_eq!(region.range(), 2..8);
+}
+
+#[test]
+fn test_shift_delete_file_scope() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.shift_delete(1, 2, ShiftScope::File).unwrap();
+    assert_eq!(contents(&file), b"01256789");
+    assert_eq!(region.range(), 2..6);
+    assert!(matches!(
+        region.shift_delete(3, 2, ShiftScope::File),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_shift_large_tail() {
+    let file = tempfile().unwrap();
+    let data: Vec<u8> = (0..30_000u32).map(|i| (i % 251) as u8).collect();
+    (&file).write_all(&data).unwrap();
+    let mut region = FileRegion::from_file(&file).unwrap();
+    region.shift_insert(100, b"xyz", ShiftScope::File).unwrap();
+    region.shift_delete(100, 3, ShiftScope::File).unwrap();
+    assert_eq!(contents(&file), data);
+    region.shift_delete(0, 20_000, ShiftScope::Region).unwrap();
+    let mut expected = data[20_000..].to_vec();
+    expected.resize(data.len(), 0);
+    assert_eq!(contents(&file), expected);
+}
+
+#[test]
+fn test_append_region_grows() {
+    let file = tempfile().unwrap();
+    let mut slot = AppendRegion::new(&file, 4, 8);
+    assert_eq!(slot.append(b"abc").unwrap(), 0);
+    assert_eq!(slot.append(b"de").unwrap(), 3);
+    assert_eq!(slot.len(), 5);
+    assert_eq!(slot.remaining(), 3);
+    assert_eq!(slot.region().range(), 4..9);
+    assert_eq!(contents(&file), b"\0\0\0\0abcde");
+}
+
+#[test]
+fn test_append_region_capacity() {
+    let file = tempfile().unwrap();
+    let mut slot = AppendRegion::new(&file, 0, 4);
+    slot.append(b"abc").unwrap();
+    assert!(matches!(
+        slot.append(b"de"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert!(matches!(
+        slot.write(4, b"d"),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+    slot.write(1, b"XYZ").unwrap();
+    assert_eq!(slot.len(), 4);
+    assert_eq!(contents(&file), b"aXYZ");
+}
+
+#[test]
+fn test_stats_disabled_by_default() {
+    let file = tempfile_len_10();
+    assert!(FileRegion::new(&file, 0..10).stats().is_none());
+}
+
+#[test]
+fn test_stats_count_io() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8).with_stats();
+    let mut buf = [0; 4];
+    region.read(0, &mut buf).unwrap();
+    region.read(4, &mut buf).unwrap();
+    region.write(0, b"abc").unwrap();
+    let stats = region.stats().unwrap();
+    assert_eq!(stats.reads, 2);
+    assert_eq!(stats.bytes_read, 6);
+    assert_eq!(stats.writes, 1);
+    assert_eq!(stats.bytes_written, 3);
+    region.reset_stats();
+    assert_eq!(region.stats().unwrap(), StatsSnapshot::default());
+}
+
+#[test]
+fn test_stats_shared_with_subregions() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8).with_stats();
+    let sub = region.subregion(1..3).unwrap();
+    sub.write_at(0, b"xy").unwrap();
+    assert_eq!(region.stats().unwrap().bytes_written, 2);
+}
+
+#[cfg(all(feature = "uring", target_os = "linux"))]
+#[test]
+fn test_uring_batch() {
+    use crate::uring::UringBatch;
+
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let tail = FileRegion::new(&file, 8..10);
+    let mut a = [0; 3];
+    let mut b = [0; 8];
+    let Ok(mut batch) = UringBatch::new(2) else {
+        // The kernel or sandbox does not allow io_uring.
+        return;
+    };
+    batch.read_at(&region, 0, &mut a).unwrap();
+    batch.read_at(&region, 3, &mut b).unwrap();
+    batch.write_at(&tail, 1, b"!").unwrap();
+    assert!(matches!(
+        batch.write_at(&tail, 1, b"!!"),
+        Err(RegionError::EndOutOfBounds { .. })
+    ));
+    assert_eq!(batch.len(), 3);
+    let results = batch.submit().unwrap();
+    assert_eq!(results[0].as_ref().unwrap(), &3);
+    assert_eq!(results[1].as_ref().unwrap(), &3);
+    assert_eq!(results[2].as_ref().unwrap(), &1);
+    assert_eq!(&a, b"234");
+    assert_eq!(&b[..3], b"567");
+    assert_eq!(contents(&file), b"012345678!");
+}
+
+#[test]
+fn test_aligned_buf() {
+    let buf = AlignedBuf::new(8192, 4096);
+    assert_eq!(buf.len(), 8192);
+    assert_eq!(buf.as_ptr() as usize % 4096, 0);
+    assert!(buf.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_direct_region_rejects_misaligned() {
+    let file = tempfile().unwrap();
+    file.set_len(8192).unwrap();
+    let direct = DirectRegion::new(FileRegion::new(&file, 0..8192), 512);
+    let mut buf = AlignedBuf::new(1024, 512);
+    assert!(matches!(
+        direct.read(100, &mut buf),
+        Err(FileRegionError::Region(RegionError::Misaligned))
+    ));
+    assert!(matches!(
+        direct.read(0, &mut buf[..100]),
+        Err(FileRegionError::Region(RegionError::Misaligned))
+    ));
+    assert!(matches!(
+        direct.write(0, &buf[1..513]),
+        Err(FileRegionError::Region(RegionError::Misaligned))
+    ));
+    let huge = DirectRegion::new(FileRegion::new(&file, 0..u64::MAX), 512);
+    assert!(matches!(
+        huge.read(0, &mut buf),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+}
+
+#[test]
+fn test_direct_read_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("direct.bin");
+    std::fs::write(&path, vec![1u8; 8192]).unwrap();
+    let file = match direct::open(&path, std::fs::OpenOptions::new().read(true).write(true)) {
+        Ok(file) => file,
+        // The filesystem does not support direct I/O.
+        Err(_) => return,
+    };
+    let whole = DirectRegion::new(FileRegion::new(&file, 0..8192), 4096);
+    let mut buf = AlignedBuf::new(4096, 4096);
+    buf.fill(7);
+    assert_eq!(whole.write(4096, &buf).unwrap(), 4096);
+    let partial = DirectRegion::new(FileRegion::new(&file, 4096..6000), 4096);
+    assert!(matches!(
+        partial.write(0, &buf),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    let mut buf = AlignedBuf::new(4096, 4096);
+    assert_eq!(partial.read(0, &mut buf).unwrap(), 1904);
+    assert!(buf[..1904].iter().all(|&b| b == 7));
+}
+
+#[test]
+fn test_advise() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    for advice in [
+        Advice::Normal,
+        Advice::Sequential,
+        Advice::Random,
+        Advice::WillNeed,
+        Advice::DontNeed,
+        Advice::NoReuse,
+    ] {
+        region.advise(advice).unwrap();
+    }
+    FileRegion::new(&file, 4..4)
+        .advise(Advice::DontNeed)
+        .unwrap();
+}
+
+#[test]
+fn test_follow_region_tracks_growth() {
+    let file = tempfile_len_10();
+    let follow = FollowRegion::new(&file, 4);
+    assert_eq!(follow.current_range().unwrap(), 4..10);
+    (&file).write_all(b"abc").unwrap();
+    assert_eq!(follow.region().unwrap().range(), 4..13);
+    let mut buf = [0; 16];
+    assert_eq!(follow.read_at(6, &mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"abc");
+}
+
+#[test]
+fn test_follow_region_limit() {
+    let file = tempfile_len_10();
+    let follow = FileRegion::new(&file, 2..12).follow();
+    assert_eq!(follow.current_range().unwrap(), 2..10);
+    (&file).write_all(b"abcd").unwrap();
+    assert_eq!(follow.current_range().unwrap(), 2..12);
+    assert!(matches!(
+        follow.wait_for_data(10, None),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_follow_region_wait_for_data() {
+    use std::time::Duration;
+
+    let file = tempfile_len_10();
+    let follow = FollowRegion::new(&file, 0).poll_interval(Duration::from_millis(5));
+    assert!(follow.wait_for_data(9, None).unwrap());
+    assert!(!follow
+        .wait_for_data(10, Some(Duration::from_millis(20)))
+        .unwrap());
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            std::thread::sleep(Duration::from_millis(20));
+            (&file).write_all(b"x").unwrap();
+        });
+        assert!(follow
+            .wait_for_data(10, Some(Duration::from_secs(10)))
+            .unwrap());
+    });
+}
+
+#[test]
+fn test_hexdump() {
+    let mut file = tempfile().unwrap();
+    file.write_all(b"header\0\x01\x02\xffABCDEFGHIJKLMNOP")
+        .unwrap();
+    let region = FileRegion::new(&file, 2..100);
+    assert_eq!(
+        region.hexdump(0, 100).unwrap(),
+        "00000000  61 64 65 72 00 01 02 ff  41 42 43 44 45 46 47 48  |ader....ABCDEFGH|\n\
+         00000010  49 4a 4b 4c 4d 4e 4f 50                           |IJKLMNOP|\n"
+    );
+    assert_eq!(
+        region.hexdump(17, 2).unwrap(),
+        "00000011  4a 4b                                             |JK|\n"
+    );
+    assert_eq!(region.preview(0).unwrap(), "");
+    assert!(matches!(
+        region.hexdump(99, 1),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[test]
+fn test_open_by_path() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"0123456789").unwrap();
+    let owned = FileRegion::open(file.path(), 2..6).unwrap();
+    assert_eq!(owned.path(), Some(file.path()));
+    owned.region().write_at(0, b"ab").unwrap();
+    assert_eq!(contents(file.as_file()), b"01ab456789");
+    assert!(format!("{owned:?}").contains(&format!("{:?}", file.path())));
+
+    let readonly = FileRegion::open_readonly(file.path(), 0..4).unwrap();
+    assert!(readonly.region().write_at(0, b"x").is_err());
+    assert!(matches!(
+        path_region_error(FileRegion::open(file.path(), 8..12).unwrap_err()),
+        Some(RegionError::EndOutOfBounds { .. })
+    ));
+    assert!(matches!(
+        path_region_error(FileRegion::open_readonly(file.path(), 11..12).unwrap_err()),
+        Some(RegionError::StartOutOfBounds { .. })
+    ));
+}
+
+/// Returns the `RegionError` behind a validation error from `open()`.
+fn path_region_error(error: FileRegionError) -> Option<RegionError> {
+    let FileRegionError::Io(error) = error else {
+        return None;
+    };
+    match std::error::Error::source(&error)?.downcast_ref::<FileRegionError>()? {
+        FileRegionError::Region(error) => Some(*error),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_open_by_path_error_includes_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing.bin");
+    match FileRegion::open_readonly(&path, 0..4) {
+        Err(FileRegionError::Io(err)) => {
+            assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+            assert!(err.to_string().contains("missing.bin"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    let path = dir.path().join("short.bin");
+    std::fs::write(&path, b"01").unwrap();
+    match FileRegion::open_readonly(&path, 0..4) {
+        Err(FileRegionError::Io(err)) => {
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains("short.bin"));
+            assert!(err.to_string().contains("out of bounds"));
+            assert!(matches!(
+                path_region_error(FileRegionError::Io(err)),
+                Some(RegionError::EndOutOfBounds { end: 4, limit: 2 })
+            ));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[cfg(feature = "positioned-io")]
+#[test]
+fn test_positioned_io_traits() {
+    use positioned_io::{ReadAt, Size, WriteAt};
+
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..12);
+    assert_eq!(region.size().unwrap(), Some(8));
+    let mut buf = [0; 4];
+    ReadAt::read_exact_at(&region, 1, &mut buf).unwrap();
+    assert_eq!(&buf, b"3456");
+    assert_eq!(ReadAt::read_at(&region, 10, &mut buf).unwrap(), 0);
+    WriteAt::write_all_at(&mut region, 8, b"ab").unwrap();
+    assert_eq!(contents(&file), b"0123456789ab");
+    let err = WriteAt::write_all_at(&mut region, 9, b"cd").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_preallocate() {
+    let file = tempfile_len_10();
+    FileRegion::new(&file, 2..6).preallocate().unwrap();
+    assert_eq!(contents(&file), b"0123456789");
+    FileRegion::new(&file, 8..4096).preallocate().unwrap();
+    let data = contents(&file);
+    assert_eq!(data.len(), 4096);
+    assert_eq!(&data[..10], b"0123456789");
+    assert!(data[10..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_region_lock_table_conflicts() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let other = file.reopen().unwrap();
+    let table = RegionLockTable::new();
+    let a = FileRegion::new(file.as_file(), 0..10);
+    let b = FileRegion::new(&other, 5..15);
+    let c = FileRegion::new(&other, 10..20);
+
+    let shared = table.lock_shared(&a).unwrap();
+    assert!(table.try_lock_shared(&b).unwrap().is_some());
+    assert!(table.try_lock_exclusive(&b).unwrap().is_none());
+    let exclusive = table.try_lock_exclusive(&c).unwrap().unwrap();
+    assert!(table
+        .try_lock_shared(&FileRegion::new(&other, 12..12))
+        .unwrap()
+        .is_some());
+    drop(shared);
+    assert!(table.try_lock_exclusive(&a).unwrap().is_some());
+    assert!(table.try_lock_shared(&c).unwrap().is_none());
+    drop(exclusive);
+    assert!(table.try_lock_shared(&c).unwrap().is_some());
+}
+
+#[test]
+fn test_region_lock_table_blocks() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    let file = tempfile_len_10();
+    let table = RegionLockTable::new();
+    let released = AtomicBool::new(false);
+    let guard = table.lock_exclusive(&FileRegion::new(&file, 0..5)).unwrap();
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let _guard = table.lock_exclusive(&FileRegion::new(&file, 4..8)).unwrap();
+            assert!(released.load(Ordering::SeqCst));
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        released.store(true, Ordering::SeqCst);
+        drop(guard);
+    });
+}
+
+#[test]
+fn test_verified_region() {
+    let mut file = tempfile().unwrap();
+    file.write_all(b"123456789\0\0\0\0").unwrap();
+    let data = FileRegion::new(&file, 0..9);
+    let mut verified = VerifiedRegion::new(data, FileRegion::new(&file, 9..13)).unwrap();
+    verified.seal().unwrap();
+    assert_eq!(&contents(&file)[9..], &0xcbf4_3926_u32.to_le_bytes());
+    verified.verify().unwrap();
+    let mut buf = [0; 4];
+    assert_eq!(verified.read(7, &mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"89");
+
+    verified.write_all(0, b"abc").unwrap();
+    assert_eq!(verified.read_to_vec().unwrap(), b"abc456789");
+}
+
+#[test]
+fn test_verified_region_detects_corruption() {
+    let file = tempfile_len_10();
+    let mut verified =
+        VerifiedRegion::new(FileRegion::new(&file, 0..6), FileRegion::new(&file, 6..10)).unwrap();
+    verified.seal().unwrap();
+    FileRegion::new(&file, 0..6).write_at(2, b"X").unwrap();
+    assert!(matches!(
+        verified.verify(),
+        Err(FileRegionError::Integrity(IntegrityError { .. }))
+    ));
+    let mut buf = [0; 1];
+    assert!(matches!(
+        verified.read(0, &mut buf),
+        Err(FileRegionError::Integrity(_))
+    ));
+    assert!(matches!(
+        VerifiedRegion::new(FileRegion::new(&file, 0..6), FileRegion::new(&file, 6..9)),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+}
+
+#[test]
+fn test_journaled_region_undo() {
+    let file = tempfile_len_10();
+    let journal = tempfile().unwrap();
+    let mut journaled = JournaledRegion::new(FileRegion::new(&file, 2..8), &journal);
+    journaled.write(0, b"ab").unwrap();
+    journaled.write(1, b"XYZ").unwrap();
+    assert_eq!(contents(&file), b"01aXYZ6789");
+    assert!(journaled.is_dirty().unwrap());
+
+    // A fresh wrapper recovers from the journal alone, as after a crash.
+    let mut recovered = JournaledRegion::new(FileRegion::new(&file, 2..8), &journal);
+    recovered.undo().unwrap();
+    assert_eq!(contents(&file), b"0123456789");
+    assert!(!recovered.is_dirty().unwrap());
+}
+
+#[test]
+fn test_journaled_region_commit() {
+    let file = tempfile_len_10();
+    let mut journal = tempfile().unwrap();
+    let mut journaled = JournaledRegion::new(FileRegion::new(&file, 2..8), &journal);
+    journaled.write(4, b"ab").unwrap();
+    assert!(matches!(
+        journaled.write(5, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    journaled.commit().unwrap();
+    assert!(!journaled.is_dirty().unwrap());
+    journaled.undo().unwrap();
+    assert_eq!(contents(&file), b"012345ab89");
+
+    // A torn record at the end of the journal is ignored.
+    journal.write_all(&[1, 0, 0]).unwrap();
+    JournaledRegion::new(FileRegion::new(&file, 2..8), &journal)
+        .undo()
+        .unwrap();
+    assert_eq!(contents(&file), b"012345ab89");
+}
+
+#[test]
+fn test_journaled_region_ignores_corrupt_tail() {
+    let file = tempfile_len_10();
+    let mut journal = tempfile().unwrap();
+    let mut journaled = JournaledRegion::new(FileRegion::new(&file, 2..8), &journal);
+    journaled.write(0, b"ab").unwrap();
+    assert!(matches!(
+        journaled.write(u64::MAX, b"ab"),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+
+    // A record whose header parses but whose checksum does not match, as a
+    // zero-filled or torn tail would, is not replayed.
+    let mut bogus = Vec::new();
+    bogus.extend_from_slice(&2u64.to_le_bytes());
+    bogus.extend_from_slice(&2u64.to_le_bytes());
+    bogus.extend_from_slice(&0u32.to_le_bytes());
+    bogus.extend_from_slice(b"zz");
+    journal.seek(SeekFrom::End(0)).unwrap();
+    journal.write_all(&bogus).unwrap();
+    journal.write_all(&[0; 40]).unwrap();
+    JournaledRegion::new(FileRegion::new(&file, 2..8), &journal)
+        .undo()
+        .unwrap();
+    assert_eq!(contents(&file), b"0123456789");
+}
+
+#[test]
+fn test_read_uninit() {
+    use std::mem::MaybeUninit;
+
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    let mut buf = [MaybeUninit::uninit(); 8];
+    assert_eq!(region.read_uninit(1, &mut buf).unwrap(), b"345");
+    let mut spare = Vec::<u8>::with_capacity(16);
+    let n = region
+        .read_uninit(0, spare.spare_capacity_mut())
+        .unwrap()
+        .len();
+    // SAFETY: `read_uninit` initialized the first `n` bytes.
+    unsafe { spare.set_len(n) };
+    assert_eq!(spare, b"2345");
+    assert!(matches!(
+        region.read_uninit(4, &mut buf),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+}
+
+#[cfg(all(feature = "tokio-uring", target_os = "linux"))]
+#[test]
+fn test_async_uring_region() {
+    use crate::async_uring::UringRegion;
+
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(b"0123456789").unwrap();
+    tokio_uring::start(async {
+        let uring_file = tokio_uring::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(file.path())
+            .await
+            .unwrap();
+        let region = UringRegion::new(&uring_file, 2..6);
+        let (result, buf) = region.read_at(1, vec![0; 8]).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(&buf[..3], b"345");
+        let (result, _) = region.read_at(4, buf).await;
+        assert!(matches!(
+            result,
+            Err(FileRegionError::Region(
+                RegionError::StartOutOfBounds { .. }
+            ))
+        ));
+        let (result, _) = region.write_all_at(2, b"ab".to_vec()).await;
+        result.unwrap();
+        let (result, _) = region.write_at(3, b"ab".to_vec()).await;
+        assert!(matches!(
+            result,
+            Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+        ));
+        uring_file.close().await.unwrap();
+    });
+    assert_eq!(contents(file.as_file()), b"0123ab6789");
+}
+
+/// A toy codec that XORs each byte with its block index plus a key.
+struct XorCodec {
+    block_size: usize,
+    key: u8,
+}
+
+impl RegionCodec for XorCodec {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn decode(&self, index: u64, block: &mut [u8]) {
+        self.encode(index, block);
+    }
+
+    fn encode(&self, index: u64, block: &mut [u8]) {
+        for byte in block {
+            *byte ^= self.key.wrapping_add(index as u8);
+        }
+    }
+}
+
+#[test]
+fn test_transformed_region_round_trip() {
+    let file = tempfile().unwrap();
+    file.set_len(12).unwrap();
+    let codec = XorCodec {
+        block_size: 4,
+        key: 0x20,
+    };
+    let region = TransformedRegion::new(FileRegion::new(&file, 0..12), codec).unwrap();
+    region.write(0, b"abcdefghijkl").unwrap();
+    assert_eq!(contents(&file), b"ABCDDGFIKHIN");
+    region.write(3, b"XYZ").unwrap();
+    let mut buf = [0; 16];
+    assert_eq!(region.read(2, &mut buf).unwrap(), 10);
+    assert_eq!(&buf[..10], b"cXYZghijkl");
+    assert_eq!(region.read(4, &mut buf[..2]).unwrap(), 2);
+    assert_eq!(&buf[..2], b"YZ");
+}
+
+#[test]
+fn test_transformed_region_bounds() {
+    let file = tempfile_len_10();
+    let codec = || XorCodec {
+        block_size: 4,
+        key: 1,
+    };
+    assert!(matches!(
+        TransformedRegion::new(FileRegion::new(&file, 0..10), codec()),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+    // The file ends inside the second block, so only the first is readable.
+    let region = TransformedRegion::new(FileRegion::new(&file, 4..12), codec()).unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 4);
+    assert!(matches!(
+        region.write(6, b"abc"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    region.write(6, b"ab").unwrap();
+    assert_eq!(region.read(4, &mut buf).unwrap(), 4);
+    assert_eq!(&buf[..4], b"\0\0ab");
+}
+
+#[test]
+fn test_region_allocator() {
+    let allocator = RegionAllocator::new(tempfile().unwrap(), 10).unwrap();
+    assert_eq!(allocator.file().metadata().unwrap().len(), 10);
+    let regions: Vec<_> = (0..3).map(|_| allocator.alloc(3).unwrap()).collect();
+    assert_eq!(allocator.remaining(), 1);
+    assert!(matches!(
+        allocator.alloc(2),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    std::thread::scope(|scope| {
+        for (i, owned) in regions.into_iter().enumerate() {
+            scope.spawn(move || {
+                owned.region().write_at(0, &[b'a' + i as u8; 3]).unwrap();
+            });
+        }
+    });
+    let last = allocator.alloc(1).unwrap();
+    assert_eq!(last.range(), 9..10);
+    assert_eq!(contents(allocator.file()), b"aaabbbccc\0");
+}
+
+#[test]
+fn test_common_prefix_len() {
+    let mut file = tempfile().unwrap();
+    file.write_all(b"abcdefabcxyzabc").unwrap();
+    let a = FileRegion::new(&file, 0..6);
+    let b = FileRegion::new(&file, 6..12);
+    assert_eq!(a.common_prefix_len(&b).unwrap(), 3);
+    assert_eq!(a.first_mismatch(&b).unwrap(), Some(3));
+    let c = FileRegion::new(&file, 12..20);
+    assert_eq!(a.common_prefix_len(&c).unwrap(), 3);
+    assert_eq!(c.first_mismatch(&a).unwrap(), Some(3));
+    assert_eq!(
+        a.first_mismatch(&FileRegion::new(&file, 0..6)).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_first_mismatch_across_chunks() {
+    let data = vec![7; 20_000];
+    let mut file = tempfile().unwrap();
+    file.write_all(&data).unwrap();
+    file.write_all(&data).unwrap();
+    let a = FileRegion::new(&file, 0..20_000);
+    let b = FileRegion::new(&file, 20_000..40_000);
+    assert_eq!(a.first_mismatch(&b).unwrap(), None);
+    b.write(12_345, b"x").unwrap();
+    assert_eq!(a.first_mismatch(&b).unwrap(), Some(12_345));
+    assert_eq!(a.common_prefix_len(&b).unwrap(), 12_345);
+}
+
+#[test]
+fn test_records() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 1..10);
+    let records: Vec<_> = region.records(3).unwrap().map(|r| r.range()).collect();
+    assert_eq!(records, [1..4, 4..7, 7..10]);
+    assert!(matches!(
+        region.records(2),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+}
+
+#[test]
+fn test_records_with() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 0..8);
+    let sums: Vec<u32> = region
+        .records_with(4, |bytes| bytes.iter().map(|&b| u32::from(b - b'0')).sum())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(sums, [6, 22]);
+
+    let past_end = FileRegion::new(&file, 6..12);
+    let mut records = past_end.records_with(3, |bytes| bytes.to_vec()).unwrap();
+    assert_eq!(records.next().unwrap().unwrap(), b"678");
+    assert!(matches!(
+        records.next(),
+        Some(Err(FileRegionError::ShortRead(ShortReadError {
+            requested: 3,
+            read: 1
+        })))
+    ));
+}
+
+#[test]
+fn test_region_buf_writer_coalesces() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..10).with_stats();
+    let mut writer = RegionBufWriter::with_capacity(region, 4);
+    writer.write(0, b"a").unwrap();
+    writer.write(1, b"bc").unwrap();
+    assert_eq!(writer.buffer(), b"abc");
+    assert_eq!(contents(&file), b"0123456789");
+    writer.write(3, b"de").unwrap();
+    assert_eq!(writer.buffer(), b"de");
+    writer.write(6, b"x").unwrap();
+    assert_eq!(writer.buffer(), b"x");
+    writer.write(0, b"ABCD").unwrap();
+    assert!(writer.buffer().is_empty());
+    let region = writer.into_inner().unwrap();
+    assert_eq!(contents(&file), b"01ABCDe7x9");
+    assert_eq!(region.stats().unwrap().writes, 4);
+}
+
+#[test]
+fn test_region_buf_writer_bounds() {
+    let file = tempfile_len_10();
+    let mut writer = RegionBufWriter::new(FileRegion::new(&file, 2..6));
+    writer.write(0, b"ab").unwrap();
+    assert!(matches!(
+        writer.write(2, b"cde"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    drop(writer);
+    assert_eq!(contents(&file), b"01ab456789");
+}
+
+#[test]
+fn test_try_from_conversions() {
+    let file = tempfile_len_10();
+    let region = FileRegion::try_from((&file, 2..6)).unwrap();
+    assert_eq!((region.start(), region.end()), (2, 6));
+    assert_eq!(region.range_ref(), &(2..6));
+    assert_eq!(region.range(), 2..6);
+    assert!(matches!(
+        FileRegion::try_from((&file, 5..11)),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    let whole: FileRegion = (&file).try_into().unwrap();
+    assert_eq!(whole.range(), 0..10);
+}
+
+#[test]
+fn test_validated_region() {
+    let file = tempfile_len_10();
+    assert!(matches!(
+        FileRegion::new(&file, 2..8).with_known_len(7),
+        Err(RegionError::EndOutOfBounds { .. })
+    ));
+    let mut validated = ValidatedRegion::new(FileRegion::new(&file, 2..8)).unwrap();
+    assert_eq!(validated.known_len(), 10);
+    validated.checked_write(0, b"ab").unwrap();
+    let mut buf = [0; 4];
+    assert_eq!(validated.checked_read(0, &mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"ab45");
+
+    // The cache hides the truncation until it is refreshed.
+    file.set_len(5).unwrap();
+    assert!(validated.is_valid());
+    assert_eq!(validated.refresh_len().unwrap(), 5);
+    assert!(matches!(
+        validated.validate(),
+        Err(RegionError::EndOutOfBounds { .. })
+    ));
+    assert!(matches!(
+        validated.checked_read(0, &mut buf),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert_eq!(validated.into_inner().range(), 2..8);
+}
+
+#[test]
+fn test_validated_checked_write_writes_all() {
+    let file = tempfile().unwrap();
+    file.set_len(100_000).unwrap();
+    let validated = FileRegion::from_file(&file)
+        .unwrap()
+        .with_known_len(100_000)
+        .unwrap();
+    let data = vec![7u8; 100_000];
+    assert_eq!(validated.checked_write(0, &data).unwrap(), data.len());
+    assert_eq!(contents(&file), data);
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn test_watch() {
+    use std::time::Duration;
+
+    use crate::RegionEvent;
+
+    let mut named = tempfile::NamedTempFile::new().unwrap();
+    named.write_all(b"0123456789").unwrap();
+    let file = named.as_file();
+    let region = FileRegion::new(file, 2..6);
+    let mut watcher = region.watch(named.path()).unwrap();
+    assert_eq!(watcher.range(), 2..6);
+
+    region.write(0, b"ab").unwrap();
+    let timeout = Duration::from_secs(5);
+    assert_eq!(
+        watcher.recv_timeout(timeout).unwrap(),
+        Some(RegionEvent::Modified)
+    );
+
+    // Growth past the end of the region is a plain modification; truncation
+    // into it is reported as such.
+    file.set_len(20).unwrap();
+    assert_eq!(
+        watcher.recv_timeout(timeout).unwrap(),
+        Some(RegionEvent::Modified)
+    );
+    file.set_len(4).unwrap();
+    let event = loop {
+        match watcher.recv_timeout(timeout).unwrap() {
+            Some(RegionEvent::Modified) => continue,
+            event => break event,
+        }
+    };
+    assert_eq!(event, Some(RegionEvent::Truncated { len: 4 }));
+}
+
+#[test]
+fn test_std_io_traits() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    let mut text = String::new();
+    Read::read_to_string(&mut region, &mut text).unwrap();
+    assert_eq!(text, "234567");
+    assert_eq!(region.seek(SeekFrom::End(-2)).unwrap(), 4);
+    let mut buf = [0; 4];
+    assert_eq!(Read::read(&mut region, &mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"67");
+    assert_eq!((&file).stream_position().unwrap(), 10);
+
+    region.seek(SeekFrom::Start(1)).unwrap();
+    Write::write_all(&mut region, b"abc").unwrap();
+    assert_eq!(region.stream_position().unwrap(), 4);
+    let err = Write::write_all(&mut region, b"xyz").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(contents(&file), b"012abcxy89");
+}
+
+#[test]
+fn test_seek_clamps_to_region() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    assert_eq!(region.seek(SeekFrom::Start(100)).unwrap(), 6);
+    assert_eq!(region.seek(SeekFrom::Current(-100)).unwrap(), 0);
+    assert_eq!(region.seek(SeekFrom::End(5)).unwrap(), 6);
+    let mut copy = Vec::new();
+    region.rewind().unwrap();
+    std::io::copy(&mut region, &mut copy).unwrap();
+    assert_eq!(copy, b"234567");
+}
+
+#[test]
+fn test_buf_reader_lines() {
+    use std::io::BufRead;
+
+    let mut file = tempfile().unwrap();
+    file.write_all(b"skip\nalpha\nbeta\ngamma\nrest").unwrap();
+    let reader = FileRegion::new(&file, 5..18).buf_reader();
+    assert_eq!(reader.capacity(), 13);
+    let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["alpha", "beta", "ga"]);
+}
+
+#[test]
+fn test_region_cursors_are_independent() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let mut a = region.cursor();
+    let mut b = region.cursor();
+    let mut buf = [0; 2];
+    a.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"23");
+    b.set_position(3);
+    b.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"56");
+    assert_eq!((a.position(), b.position()), (2, 5));
+    a.advance(-1);
+    a.write_all(b"x").unwrap();
+    assert_eq!(a.remaining(), 4);
+    b.set_position(100);
+    assert_eq!(b.position(), 6);
+    b.rewind();
+    assert_eq!(b.seek(SeekFrom::End(-1)).unwrap(), 5);
+    assert_eq!(contents(&file), b"012x456789");
+}
+
+#[test]
+// The stats counters are interior-mutable but take no part in `Hash` or `Eq`.
+#[allow(clippy::mutable_key_type)]
+fn test_clone_eq_hash() {
+    use std::collections::HashSet;
+
+    let file = tempfile_len_10();
+    let other = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6).with_stats();
+    let clone = region.clone();
+    assert_eq!(region, clone);
+    assert_eq!(region, FileRegion::new(&file, 2..6));
+    assert_ne!(region, FileRegion::new(&file, 2..7));
+    assert_ne!(region, FileRegion::new(&other, 2..6));
+    let set: HashSet<_> = [
+        clone,
+        FileRegion::new(&file, 2..6),
+        FileRegion::new(&other, 2..6),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 2);
+    assert!(format!("{region:?}").contains("range: 2..6"));
+}
+
+#[test]
+fn test_display() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    assert_eq!(region.to_string(), "FileRegion(2..8, len=6)");
+    let child = region.subregion(1..3).unwrap();
+    let pretty = format!("{child:#?}");
+    assert!(pretty.contains("len: 2"));
+    assert!(pretty.contains("ancestors"));
+    assert!(!format!("{child:?}").contains("ancestors"));
+}
+
+#[test]
+fn test_range_bounds() {
+    let file = tempfile_len_10();
+    assert_eq!(FileRegion::try_new(&file, 4..).unwrap().range(), 4..10);
+    assert_eq!(FileRegion::try_new(&file, ..=2).unwrap().range(), 0..3);
+    assert_eq!(FileRegion::try_new(&file, ..).unwrap().range(), 0..10);
+    assert!(matches!(
+        FileRegion::try_new(&file, ..=u64::MAX),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+    let region = FileRegion::new(&file, 2..8);
+    assert_eq!(region.subregion(..3).unwrap().range(), 2..5);
+    assert_eq!(region.subregion(4..).unwrap().range(), 6..8);
+    assert_eq!(region.subregion(1..=2).unwrap().range(), 3..5);
+    assert_eq!(region.subregion(..).unwrap().range(), 2..8);
+}
+
+#[test]
+fn test_subregion_borrows_parent() {
+    let file = tempfile_len_10();
+    let parent = FileRegion::new(&file, 2..8);
+    let children: Vec<_> = (0..3)
+        .map(|i| parent.subregion(i * 2..i * 2 + 2).unwrap())
+        .collect();
+    assert_eq!(
+        children.iter().map(FileRegion::range).collect::<Vec<_>>(),
+        [2..4, 4..6, 6..8]
+    );
+    assert_eq!(parent.range(), 2..8);
+}
+
+#[test]
+fn test_chunks() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 1..9);
+    let chunks: Vec<_> = region.chunks(3).map(|c| c.range()).collect();
+    assert_eq!(chunks, [1..4, 4..7, 7..9]);
+    assert_eq!(region.chunks(8).count(), 1);
+    assert_eq!(
+        region
+            .clone()
+            .with_ancestry()
+            .chunks(3)
+            .next_back()
+            .unwrap()
+            .ancestors()
+            .next(),
+        Some(&(1..9))
+    );
+    assert_eq!(FileRegion::new(&file, 4..4).chunks(3).count(), 0);
+}
+
+#[test]
+fn test_split_into() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 0..10);
+    let parts: Vec<_> = region.split_into(4).iter().map(FileRegion::range).collect();
+    assert_eq!(parts, [0..3, 3..6, 6..8, 8..10]);
+    let parts: Vec<_> = FileRegion::new(&file, 2..4)
+        .split_into(3)
+        .iter()
+        .map(FileRegion::len)
+        .collect();
+    assert_eq!(parts, [1, 1, 0]);
+}
+
+#[test]
+fn test_read_exact() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 6..12);
+    let mut buf = [0; 3];
+    region.read_exact(1, &mut buf).unwrap();
+    assert_eq!(&buf, b"789");
+    let mut buf = [0; 5];
+    assert!(matches!(
+        region.read_exact(1, &mut buf),
+        Err(FileRegionError::ShortRead(ShortReadError {
+            requested: 5,
+            read: 3
+        }))
+    ));
+    assert_eq!(&buf[..3], b"789");
+    assert!(matches!(
+        region.read_exact(2, &mut buf),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_write_all() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    region.write_all(1, b"abc").unwrap();
+    assert_eq!(contents(&file), b"012abc6789");
+    assert!(matches!(
+        region.write_all(2, b"abc"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert_eq!(contents(&file), b"012abc6789");
+}
+
+#[test]
+fn test_read_to_vec() {
+    let file = tempfile_len_10();
+    assert_eq!(FileRegion::new(&file, 2..6).read_to_vec().unwrap(), b"2345");
+    assert_eq!(FileRegion::new(&file, 7..20).read_to_vec().unwrap(), b"789");
+    let mut buf = b"xy".to_vec();
+    assert_eq!(
+        FileRegion::new(&file, 0..3).read_to_end(&mut buf).unwrap(),
+        3
+    );
+    assert_eq!(buf, b"xy012");
+    assert_eq!(
+        FileRegion::new(&file, 4..4).read_to_end(&mut buf).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_read_to_string() {
+    let mut file = tempfile().unwrap();
+    file.write_all(b"name=value\xff\xfe").unwrap();
+    assert_eq!(
+        FileRegion::new(&file, 5..10).read_to_string().unwrap(),
+        "value"
+    );
+    match FileRegion::new(&file, 5..12).read_to_string() {
+        Err(FileRegionError::Utf8(error)) => assert_eq!(error.into_bytes(), b"value\xff\xfe"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+    let index = FileRegion::new(&file, 0..12).build_line_index().unwrap();
+    assert!(matches!(index.line(0), Err(FileRegionError::Utf8(_))));
+}
+
+#[test]
+fn test_fill_and_zero() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    region.subregion(1..3).unwrap().fill(b'x').unwrap();
+    assert_eq!(contents(&file), b"012xx56789");
+    FileRegion::new(&file, 8..20_000).zero().unwrap();
+    let data = contents(&file);
+    assert_eq!(data.len(), 20_000);
+    assert!(data[8..].iter().all(|&byte| byte == 0));
+    region.fill(b'-').unwrap();
+    assert_eq!(&contents(&file)[..10], b"01------\0\0");
+}
+
+#[test]
+fn test_copy_from_reader() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..6);
+    assert_eq!(region.copy_from_reader(&mut &b"ab"[..]).unwrap(), 2);
+    assert_eq!(region.copy_from_reader(&mut &b"wxyz"[..]).unwrap(), 4);
+    assert_eq!(contents(&file), b"01wxyz6789");
+    assert!(matches!(
+        region.copy_from_reader(&mut &b"ABCDE"[..]),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_copy_to_overlapping() {
+    let file = tempfile_len_10();
+    let src = FileRegion::new(&file, 2..7);
+    assert_eq!(src.copy_to(&FileRegion::new(&file, 4..9)).unwrap(), 5);
+    assert_eq!(contents(&file), b"0123234569");
+    let src = FileRegion::new(&file, 4..9);
+    src.copy_to(&FileRegion::new(&file, 1..6)).unwrap();
+    assert_eq!(contents(&file), b"0234564569");
+}
+
+#[test]
+fn test_copy_to_other_file() {
+    let file = tempfile_len_10();
+    let other = tempfile().unwrap();
+    FileRegion::new(&file, 3..8)
+        .copy_to(&FileRegion::new(&other, 2..9))
+        .unwrap();
+    assert_eq!(contents(&other), b"\x00\x0034567");
+    assert!(matches!(
+        FileRegion::new(&file, 0..5).copy_to(&FileRegion::new(&other, 0..4)),
+        Err(FileRegionError::Region(RegionError::LengthMismatch))
+    ));
+}
+
+#[test]
+fn test_lines_iterator() {
+    let mut file = tempfile().unwrap();
+    file.write_all(b"drop\nfirst\n\nthird\xff\nfourth\nnext")
+        .unwrap();
+    let region = FileRegion::new(&file, 5..25);
+    let lines: Vec<_> = region.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0].as_deref().unwrap(), "first");
+    assert_eq!(lines[1].as_deref().unwrap(), "");
+    assert!(matches!(lines[2], Err(FileRegionError::Utf8(_))));
+    assert_eq!(lines[3].as_deref().unwrap(), "fourth");
+    assert_eq!(FileRegion::new(&file, 5..12).lines().count(), 2);
+}
+
+#[test]
+fn test_vectored_at() {
+    use std::io::{IoSlice, IoSliceMut};
+
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let (mut head, mut body) = ([0; 2], [0; 8]);
+    let n = region
+        .read_vectored_at(
+            1,
+            &mut [IoSliceMut::new(&mut head), IoSliceMut::new(&mut body)],
+        )
+        .unwrap();
+    assert_eq!(n, 5);
+    assert_eq!((&head, &body[..3]), (b"34", &b"567"[..]));
+
+    let written = region
+        .write_vectored_at(0, &[IoSlice::new(b"ab"), IoSlice::new(b"cde")])
+        .unwrap();
+    assert_eq!(written, 5);
+    assert_eq!(contents(&file), b"01abcde789");
+    assert!(matches!(
+        region.write_vectored_at(3, &[IoSlice::new(b"ab"), IoSlice::new(b"cd")]),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_varint_round_trip() {
+    let file = tempfile().unwrap();
+    let region = FileRegion::new(&file, 0..32);
+    assert_eq!(region.write_varint(0, 300).unwrap(), 2);
+    assert_eq!(&contents(&file), &[0xac, 0x02]);
+    assert_eq!(region.write_varint(2, u64::MAX).unwrap(), 10);
+    assert_eq!(region.write_varint_signed(12, -3).unwrap(), 1);
+    assert_eq!(region.read_varint(0).unwrap(), (300, 2));
+    assert_eq!(region.read_varint(2).unwrap(), (u64::MAX, 10));
+    assert_eq!(region.read_varint_signed(12).unwrap(), (-3, 1));
+}
+
+#[test]
+fn test_varint_errors() {
+    let mut file = tempfile().unwrap();
+    file.write_all(&[0x80, 0x80, 0x80]).unwrap();
+    assert!(matches!(
+        FileRegion::new(&file, 0..2).read_varint(0),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert!(matches!(
+        FileRegion::new(&file, 0..8).read_varint(0),
+        Err(FileRegionError::ShortRead(_))
+    ));
+    file.write_all(&[0xff; 8]).unwrap();
+    assert!(matches!(
+        FileRegion::new(&file, 0..11).read_varint(0),
+        Err(FileRegionError::Io(err)) if err.kind() == std::io::ErrorKind::InvalidData
+    ));
+}
+
+#[test]
+fn test_error_display_and_source() {
+    use std::error::Error;
+
+    let error = FileRegionError::Region(RegionError::EndOutOfBounds { end: 15, limit: 10 });
+    assert_eq!(
+        error.to_string(),
+        "region error: end 15 is out of bounds (limit 10)"
+    );
+    assert!(error.source().unwrap().is::<RegionError>());
+    let error = FileRegionError::ShortRead(ShortReadError {
+        requested: 8,
+        read: 3,
+    });
+    assert_eq!(error.to_string(), "short read: requested 8 bytes, read 3");
+    assert!(error.source().is_none());
+
+    fn boxed(file: &File) -> Result<(), Box<dyn Error>> {
+        FileRegion::try_new(file, 5..15)?;
+        Ok(())
+    }
+    let file = tempfile_len_10();
+    assert!(boxed(&file).unwrap_err().is::<FileRegionError>());
+}
+
+#[test]
+fn test_out_of_bounds_context() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    assert!(matches!(
+        region.read(6, &mut [0; 1]),
+        Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+            start: 6,
+            limit: 6
+        }))
+    ));
+    assert!(matches!(
+        region.write(4, b"abc"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+            end: 7,
+            limit: 6
+        }))
+    ));
+    assert!(matches!(
+        FileRegion::try_new(&file, 5..15),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+            end: 15,
+            limit: 10
+        }))
+    ));
+}
+
+#[test]
+fn test_into_io_error() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let error = std::io::Error::from(region.read(6, &mut [0; 1]).unwrap_err());
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    let inner = error.get_ref().unwrap().downcast_ref::<FileRegionError>();
+    assert!(matches!(
+        inner,
+        Some(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+    let error = std::io::Error::from(FileRegionError::ShortRead(ShortReadError {
+        requested: 4,
+        read: 1,
+    }));
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_error_kind() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8);
+    let error = region.read(6, &mut [0; 1]).unwrap_err();
+    assert!(error.is_out_of_bounds());
+    assert_eq!(
+        error.kind(),
+        ErrorKind::Region(RegionError::StartOutOfBounds { start: 6, limit: 6 })
+    );
+    let error = FileRegionError::Io(std::io::ErrorKind::WriteZero.into());
+    assert_eq!(error.kind(), ErrorKind::Io(std::io::ErrorKind::WriteZero));
+    assert!(!error.is_out_of_bounds());
+    assert_eq!(RegionError::Misaligned, RegionError::Misaligned);
+}
+
+#[test]
+fn test_oob_policy_strict() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 2..8).with_oob_policy(OobPolicy::Strict);
+    let mut buf = [0; 4];
+    assert!(matches!(
+        region.read(4, &mut buf),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+            end: 8,
+            limit: 6
+        }))
+    ));
+    assert_eq!(region.read(2, &mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"4567");
+    assert_eq!(
+        region.subregion(1..3).unwrap().oob_policy(),
+        OobPolicy::Strict
+    );
+}
+
+#[test]
+fn test_oob_policy_clamp() {
+    let file = tempfile_len_10();
+    let region = FileRegion::builder(&file)
+        .range(2..8)
+        .oob_policy(OobPolicy::Clamp)
+        .build()
+        .unwrap();
+    assert_eq!(region.write(4, b"abcd").unwrap(), 2);
+    assert_eq!(region.write_at(5, b"XY").unwrap(), 1);
+    assert_eq!(&contents(&file), b"012345aX89");
+    assert!(matches!(
+        region.write(6, b"z"),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+    assert!(matches!(
+        region.write_all(4, b"abcd"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert_eq!(&contents(&file), b"012345aX89");
+}
+
+#[test]
+fn test_oob_policy_zero_fill() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 6..14).with_oob_policy(OobPolicy::ZeroFill);
+    let mut buf = [0xff; 10];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 8);
+    assert_eq!(&buf[..8], b"6789\x00\x00\x00\x00");
+    let mut buf = [std::mem::MaybeUninit::uninit(); 4];
+    assert_eq!(region.read_uninit(3, &mut buf).unwrap(), b"9\x00\x00\x00");
+}
+
+#[test]
+fn test_write_extend() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 12..20);
+    region.write_extend(2, b"ab").unwrap();
+    assert_eq!(file.metadata().unwrap().len(), 20);
+    assert_eq!(region.read_to_vec().unwrap(), b"\x00\x00ab\x00\x00\x00\x00");
+    assert!(matches!(
+        region.write_extend(7, b"xy"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_append_grows_region_and_file() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 6..10);
+    assert_eq!(region.append(b"ab").unwrap(), 6);
+    assert_eq!(region.range(), 6..12);
+    assert_eq!(&contents(&file), b"0123456789ab");
+    assert_eq!(region.append(b"").unwrap(), 6);
+    let mut region = FileRegion::new(&file, 0..u64::MAX);
+    assert!(matches!(
+        region.append(b"x"),
+        Err(FileRegionError::Region(RegionError::EndOverflow))
+    ));
+    assert_eq!(region.range(), 0..u64::MAX);
+    #[allow(clippy::reversed_empty_ranges)]
+    let inverted = 5..3;
+    let mut region = FileRegion::new(&file, inverted.clone());
+    assert!(matches!(
+        region.append(b"x"),
+        Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+            start: 5,
+            limit: 3
+        }))
+    ));
+    assert_eq!(region.range(), inverted);
+}
+
+#[test]
+fn test_truncate() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.truncate(4).unwrap();
+    assert_eq!(region.range(), 2..6);
+    assert!(matches!(
+        region.truncate(5),
+        Err(RegionError::EndOutOfBounds { end: 5, limit: 4 })
+    ));
+    region.truncate_file(2).unwrap();
+    assert_eq!(file.metadata().unwrap().len(), 10);
+    let mut region = FileRegion::new(&file, 4..10);
+    region.truncate_file(3).unwrap();
+    assert_eq!(region.range(), 4..7);
+    assert_eq!(&contents(&file), b"0123456");
+}
+
+#[test]
+fn test_resize_grow_shrink() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..8);
+    region.grow_by(4).unwrap();
+    assert_eq!(region.range(), 2..12);
+    region.shrink_by(5).unwrap();
+    assert_eq!(region.range(), 2..7);
+    assert!(region.shrink_by(6).is_err());
+    region.resize(0).unwrap();
+    assert!(region.is_empty());
+    assert!(matches!(
+        region.resize_checked(9),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    region.resize_checked(8).unwrap();
+    assert_eq!(region.range(), 2..10);
+    assert!(matches!(
+        region.grow_by(u64::MAX),
+        Err(RegionError::EndOverflow)
+    ));
+}
+
+#[test]
+fn test_shift_bounds() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 2..5);
+    region.shift(3).unwrap();
+    assert_eq!(region.range(), 5..8);
+    region.shift(-5).unwrap();
+    assert_eq!(region.range(), 0..3);
+    assert!(matches!(region.shift(-1), Err(RegionError::StartOverflow)));
+    assert_eq!(region.range(), 0..3);
+    assert!(matches!(
+        region.shift_checked(8),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    region.shift_checked(7).unwrap();
+    assert_eq!(region.read_to_vec().unwrap(), b"789");
+}
+
+#[test]
+fn test_clamp_to_file() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 4..10);
+    assert_eq!(region.clamp_to_file().unwrap(), 10..10);
+    file.set_len(7).unwrap();
+    assert_eq!(region.clamp_to_file().unwrap(), 7..10);
+    assert_eq!(region.range(), 4..7);
+    file.set_len(2).unwrap();
+    assert_eq!(region.clamp_to_file().unwrap(), 4..7);
+    assert_eq!(region.range(), 2..2);
+}
+
+#[test]
+fn test_refresh_end() {
+    let file = tempfile_len_10();
+    let mut region = FileRegion::new(&file, 4..6);
+    assert_eq!(region.refresh_end().unwrap(), 6);
+    (&file).write_all(b"ab").unwrap();
+    assert_eq!(region.refresh_end().unwrap(), 8);
+    assert_eq!(region.read_to_vec().unwrap(), b"456789ab");
+    file.set_len(2).unwrap();
+    assert_eq!(region.refresh_end().unwrap(), 0);
+    assert_eq!(region.range(), 4..4);
+}
+
+#[test]
+fn test_owned_region_into_inner() {
+    fn open_region() -> OwnedFileRegion {
+        OwnedFileRegion::new(tempfile_len_10(), 2..6)
+    }
+    let owned = std::thread::spawn(open_region).join().unwrap();
+    assert_eq!(owned.region().read_to_vec().unwrap(), b"2345");
+    let (file, range) = owned.into_parts();
+    assert_eq!(range, 2..6);
+    let owned = OwnedFileRegion::new(file, range);
+    assert_eq!(contents(&owned.into_inner()), b"0123456789");
+}
+
+#[test]
+fn test_shared_region_across_threads() {
+    let file = std::sync::Arc::new(tempfile_len_10());
+    let region = SharedFileRegion::try_new(file, 0..10).unwrap();
+    let handles: Vec<_> = (0..2)
+        .map(|i| {
+            let half = region.subregion(i * 5..(i + 1) * 5).unwrap();
+            std::thread::spawn(move || half.write_at(0, &[b'a' + i as u8; 5]).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+    assert_eq!(region.region().read_to_vec().unwrap(), b"aaaaabbbbb");
+    assert!(region.subregion(8..12).is_err());
+}
+
+#[test]
+fn test_region_over_seek_backend() {
+    let backend = SeekBackend::new(std::io::Cursor::new(b"0123456789".to_vec()));
+    let region = FileRegion::try_new(&backend, 2..8).unwrap();
+    let mut buf = [0; 10];
+    assert_eq!(region.read(3, &mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"567");
+    assert_eq!(region.write(4, b"ab").unwrap(), 2);
+    assert!(matches!(
+        region.write(5, b"xy"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    assert!(region.read(6, &mut buf).is_err());
+    assert!(FileRegion::try_new(&backend, 8..12).is_err());
+    assert_eq!(backend.into_inner().into_inner(), b"012345ab89");
+}
+
+#[test]
+fn test_region_over_memory() {
+    let bytes = std::cell::RefCell::new(b"0123456789".to_vec());
+    let region = FileRegion::try_new(&bytes, 2..8).unwrap();
+    let mut buf = [0; 10];
+    assert_eq!(region.read(3, &mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"567");
+    region.write_at(0, b"ab").unwrap();
+    assert!(matches!(
+        region.write(5, b"xy"),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+    let sub = region.subregion(1..4).unwrap();
+    assert_eq!(sub.read_to_vec().unwrap(), b"b45");
+    assert_eq!(*bytes.borrow(), b"01ab456789");
+    assert!(matches!(
+        FileRegion::try_new(&Vec::new(), 0..4),
+        Err(FileRegionError::Region(
+            RegionError::StartOutOfBounds { .. }
+        ))
+    ));
+
+    let readonly = FileRegion::new(&b"0123456789"[..], 4..10);
+    assert_eq!(readonly.read_array::<3>(1).unwrap(), *b"567");
+    let mut stream = readonly.clone();
+    let mut text = String::new();
+    Read::read_to_string(&mut stream, &mut text).unwrap();
+    assert_eq!(text, "456789");
+
+    let shared = std::sync::Mutex::new(Vec::new());
+    FileRegion::new(&shared, 0..3).write_all(2, b"z").unwrap();
+    assert_eq!(*shared.lock().unwrap(), b"\x00\x00z");
+}
+
+#[test]
+fn test_helpers_over_memory() {
+    let bytes = std::cell::RefCell::new(b"ab\ncd\nef0123456789".to_vec());
+    let region = FileRegion::new(&bytes, 0..18);
+    assert_eq!(region.chunks(8).count(), 3);
+    assert_eq!(region.split_into(4)[1].range(), 5..10);
+    let (head, tail) = region.split_at(9).unwrap();
+    assert_eq!(head.find(0, b"cd").unwrap(), Some(3));
+    assert_eq!(tail.rfind(0, b"9").unwrap(), Some(8));
+    let index = head.build_line_index().unwrap();
+    assert_eq!(index.line(1).unwrap(), "cd");
+    let mut line = String::new();
+    index
+        .line_region(2)
+        .unwrap()
+        .cursor()
+        .read_to_string(&mut line)
+        .unwrap();
+    assert_eq!(line, "ef0");
+    assert!(head.hexdump(0, 2).unwrap().contains("|ab|"));
+
+    tail.write_u32_le(0, 7).unwrap();
+    assert_eq!(tail.read_u32_le(0).unwrap(), 7);
+    assert_eq!(tail.write_varint(4, 300).unwrap(), 2);
+    assert_eq!(tail.read_varint(4).unwrap(), (300, 2));
+    let (a, b) = (head.subregion(0..2).unwrap(), head.subregion(3..5).unwrap());
+    a.swap_with(&b).unwrap();
+    assert_eq!(&bytes.borrow()[..6], b"cd\nab\n");
+    let file = tempfile_len_10();
+    let on_disk = FileRegion::new(&file, 0..2);
+    assert_eq!(on_disk.first_mismatch(&a).unwrap(), Some(0));
+}
+
+#[test]
+fn test_region_over_file_backend() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 4..6);
+    let mut buf = [0; 4];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"45");
+    assert_eq!(RegionBackend::len(&file).unwrap(), 10);
+}
+
+#[cfg(feature = "positioned-io")]
+#[test]
+fn test_positioned_backend() {
+    use crate::PositionedBackend;
+
+    let backend = PositionedBackend(std::cell::RefCell::new(b"0123456789".to_vec()));
+    let region = FileRegion::try_new(&backend, 2..8).unwrap();
+    region.write_at(1, b"ab").unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 6);
+    assert_eq!(&buf[..6], b"2ab567");
+    let short = PositionedBackend(std::cell::RefCell::new(vec![0; 4]));
+    assert!(FileRegion::try_new(&short, 2..8).is_err());
+}
+
+#[cfg(feature = "positioned-io")]
+#[test]
+fn test_positioned_backend_read_only() {
+    use crate::PositionedBackend;
+
+    let backend = PositionedBackend(b"0123456789".to_vec());
+    let region = FileRegion::try_new(&backend, 2..8).unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 6);
+    assert_eq!(&buf[..6], b"234567");
+    let sub = region.subregion(1..3).unwrap();
+    assert_eq!(sub.read(0, &mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"34");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_read_write_take_shared_ref() {
+    let mut file = tempfile_len_10();
+    file.seek(SeekFrom::Start(1)).unwrap();
+    let region = FileRegion::new(&file, 0..10);
+    std::thread::scope(|scope| {
+        for i in 0..5 {
+            let region = &region;
+            scope.spawn(move || region.write(i * 2, b"ab").unwrap());
+        }
+    });
+    let mut buf = [0; 10];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 10);
+    assert_eq!(&buf, b"ababababab");
+    assert_eq!((&file).stream_position().unwrap(), 1);
+}
+
+#[test]
+fn test_split_at_concurrent_writes() {
+    let file = tempfile_len_10();
+    let (left, right) = FileRegion::new(&file, 0..10).split_at(4).unwrap();
+    assert_eq!((left.range(), right.range()), (0..4, 4..10));
+    std::thread::scope(|scope| {
+        scope.spawn(|| left.write_all(0, b"llll").unwrap());
+        scope.spawn(|| right.write(0, b"rrrrrr").unwrap());
+    });
+    assert_eq!(&contents(&file), b"llllrrrrrr");
+    let region = FileRegion::new(&file, 2..6);
+    assert!(matches!(
+        region.split_at(5),
+        Err(RegionError::StartOutOfBounds { start: 5, limit: 4 })
+    ));
+    let (empty, all) = region.split_at(0).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(all.range(), 2..6);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_map_chunks() {
+    let file = tempfile_len_10();
+    let region = FileRegion::new(&file, 1..10);
+    let sums = region
+        .par_map_chunks(4, |offset, bytes| {
+            (
+                offset,
+                bytes.iter().map(|&b| u32::from(b - b'0')).sum::<u32>(),
+            )
+        })
+        .unwrap();
+    assert_eq!(sums, [(0, 10), (4, 26), (8, 9)]);
+    let region = FileRegion::new(&file, 8..12);
+    assert!(matches!(
+        region.par_map_chunks(2, |_, bytes| bytes.len()),
+        Err(FileRegionError::ShortRead(_))
+    ));
+    let memory = FileRegion::new(&b"0123456789"[..], 1..10);
+    let lens = memory.par_map_chunks(4, |_, bytes| bytes.len()).unwrap();
+    assert_eq!(lens, [4, 4, 1]);
+}
+
+#[test]
+fn test_open_with_options_validates() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.bin");
+    let mut options = std::fs::OpenOptions::new();
+    options.read(true).write(true).create(true).truncate(false);
+    assert!(matches!(
+        path_region_error(FileRegion::open_with(&path, 0..4, &options).unwrap_err()),
+        Some(RegionError::StartOutOfBounds { .. })
+    ));
+    std::fs::write(&path, b"0123456789").unwrap();
+    let owned = FileRegion::open_with(&path, 2..6, &options).unwrap();
+    assert_eq!(owned.path(), Some(path.as_path()));
+    owned.region().write(0, b"ab").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"01ab456789");
+}
+
+#[test]
+fn test_region_spec_bind() {
+    let file = tempfile_len_10();
+    let specs = [
+        RegionSpec::new("data.bin", 0..3),
+        RegionSpec::new("data.bin", 7..10),
+    ];
+    let bytes: Vec<_> = specs
+        .iter()
+        .map(|spec| spec.bind(&file).unwrap().read_to_vec().unwrap())
+        .collect();
+    assert_eq!(bytes, [b"012", b"789"]);
+    assert!(matches!(
+        RegionSpec::new("data.bin", 8..12).bind(&file),
+        Err(FileRegionError::Region(RegionError::EndOutOfBounds { .. }))
+    ));
+}
+
+#[test]
+fn test_try_to_owned_dups_handle() {
+    let file = tempfile_len_10();
+    let owned = FileRegion::new(&file, 3..6).try_to_owned().unwrap();
+    drop(file);
+    let handle = std::thread::spawn(move || owned.region().read_to_vec().unwrap());
+    assert_eq!(handle.join().unwrap(), b"345");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_ext_backend() {
+    use std::os::fd::{AsFd, BorrowedFd};
+    use std::os::unix::fs::FileExt;
+
+    /// A wrapper handle, standing in for e.g. an `O_DIRECT` file type.
+    struct Wrapped(File);
+
+    impl FileExt for Wrapped {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+            FileExt::read_at(&self.0, buf, offset)
+        }
+
+        fn write_at(&self, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+            FileExt::write_at(&self.0, buf, offset)
+        }
+    }
+
+    impl AsFd for Wrapped {
+        fn as_fd(&self) -> BorrowedFd<'_> {
+            self.0.as_fd()
+        }
+    }
+
+    let backend = crate::FileExtBackend(Wrapped(tempfile_len_10()));
+    let region = FileRegion::try_new(&backend, 4..8).unwrap();
+    region.write_at(0, b"ab").unwrap();
+    let mut buf = [0; 4];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"ab67");
+    assert!(FileRegion::try_new(&backend, 8..12).is_err());
+}
+
+#[cfg(feature = "cap-std")]
+#[test]
+fn test_cap_std_backend() {
+    let file = cap_std::fs::File::from_std(tempfile_len_10());
+    let region = FileRegion::try_new(&file, 2..6).unwrap();
+    region.write_at(2, b"ab").unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(region.read(0, &mut buf).unwrap(), 4);
+    assert_eq!(&buf[..4], b"23ab");
+}
+
+#[cfg(all(feature = "ffi", unix))]
+#[test]
+fn test_ffi_region() {
+    use std::os::fd::AsRawFd;
+
+    use crate::ffi::*;
+
+    let file = tempfile_len_10();
+    let fd = file.as_raw_fd();
+    unsafe {
+        assert!(fr_region_new(fd, 2, 11).is_null());
+        assert!(fr_region_new(-1, 0, 1).is_null());
+
+        let region = fr_region_new(fd, 2, 8);
+        assert!(!region.is_null());
+        assert_eq!(fr_region_len(region), 6);
+
+        let mut buf = [0u8; 10];
+        assert_eq!(fr_region_read(region, 4, buf.as_mut_ptr(), buf.len()), 2);
+        assert_eq!(&buf[..2], b"67");
+        assert_eq!(fr_region_read(region, 6, buf.as_mut_ptr(), 1), FR_EBOUNDS);
+        assert_eq!(
+            fr_region_read(region, 0, std::ptr::null_mut(), 1),
+            FR_EINVAL
+        );
+        assert_eq!(
+            fr_region_read(region, 0, buf.as_mut_ptr(), usize::MAX),
+            FR_EINVAL
+        );
+
+        assert_eq!(fr_region_write(region, 5, b"xy".as_ptr(), 2), FR_EBOUNDS);
+        assert_eq!(
+            fr_region_write(region, u64::MAX, b"xy".as_ptr(), 2),
+            FR_EBOUNDS
+        );
+        assert_eq!(
+            fr_region_write(region, 0, b"ab".as_ptr(), usize::MAX),
+            FR_EINVAL
+        );
+        assert_eq!(fr_region_write(region, 0, b"ab".as_ptr(), 2), 2);
+
+        let sub = fr_region_subregion(region, 1, 3);
+        assert!(!sub.is_null());
+        assert!(fr_region_subregion(region, 4, 7).is_null());
+        assert_eq!(fr_region_read(sub, 0, buf.as_mut_ptr(), buf.len()), 2);
+        assert_eq!(&buf[..2], b"b4");
+
+        fr_region_free(sub);
+        fr_region_free(region);
+        fr_region_free(std::ptr::null_mut());
+    }
+    assert_eq!(contents(&file), b"01ab456789");
+    assert_eq!(crate::ffi::guard(FR_EPANIC, || panic!("boom")), FR_EPANIC);
+}
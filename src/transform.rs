@@ -0,0 +1,154 @@
+use super::core::{subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+/// A length-preserving transform applied to fixed-size blocks of a region,
+/// such as a stream or block cipher in a counter-like mode. Each block is
+/// transformed in place and may depend on its index, so a codec can derive a
+/// per-block nonce or tweak.
+///
+/// Because the encoded and decoded sizes must match, offsets map directly
+/// between the plain and stored forms; size-changing transforms such as
+/// compression need their own index and are out of scope.
+pub trait RegionCodec {
+    /// Returns the size of the blocks the codec works on. Must be nonzero.
+    /// Use 1 for byte-wise codecs.
+    fn block_size(&self) -> usize;
+
+    /// Transforms a stored block into plain bytes.
+    fn decode(&self, index: u64, block: &mut [u8]);
+
+    /// Transforms a block of plain bytes into its stored form.
+    fn encode(&self, index: u64, block: &mut [u8]);
+}
+
+/// A region whose stored bytes pass through a `RegionCodec`. Reads decode and
+/// writes encode, with offsets relative to the region as usual. Reads and
+/// writes that cover only part of a block are widened to whole blocks, so a
+/// partial write reads, decodes, patches, encodes, and rewrites its blocks.
+///
+/// Block indexes count from the start of the region.
+pub struct TransformedRegion<'a, C> {
+    region: FileRegion<'a>,
+    codec: C,
+}
+
+impl<'a, C: RegionCodec> TransformedRegion<'a, C> {
+    /// Creates a new `TransformedRegion`. Performs no I/O.
+    ///
+    /// Returns `RegionError::LengthMismatch` if the region's length is not a
+    /// multiple of the codec's block size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the codec's block size is zero.
+    pub fn new(
+        region: FileRegion<'a>,
+        codec: C,
+    ) -> Result<TransformedRegion<'a, C>, FileRegionError> {
+        let block_size = codec.block_size();
+        assert!(block_size > 0, "block size must be nonzero");
+        if !region.len().is_multiple_of(block_size as u64) {
+            return Err(FileRegionError::Region(RegionError::LengthMismatch));
+        }
+        Ok(TransformedRegion { region, codec })
+    }
+
+    /// Returns the underlying region, which holds the stored (encoded) bytes.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Returns the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns the underlying region and codec.
+    pub fn into_inner(self) -> (FileRegion<'a>, C) {
+        (self.region, self.codec)
+    }
+
+    /// Reads and decodes bytes starting at `offset`, with the bounds semantics
+    /// of `FileRegion::read()`. Returns the number of bytes read, which is
+    /// less than requested only at the end of the region or if the file ends
+    /// inside it; a trailing partial block is not returned.
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        if offset >= self.region.len() {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.region.len(),
+            }));
+        }
+        let end = offset
+            .saturating_add(buf.len() as u64)
+            .min(self.region.len());
+        let (first, mut blocks) = self.read_blocks(offset, end)?;
+        let block_size = self.codec.block_size() as u64;
+        let skip = (offset - first * block_size) as usize;
+        let complete = blocks.len() - blocks.len() % block_size as usize;
+        blocks.truncate(complete);
+        self.decode(first, &mut blocks);
+        let n = complete.saturating_sub(skip).min((end - offset) as usize);
+        buf[..n].copy_from_slice(&blocks[skip..skip + n]);
+        Ok(n)
+    }
+
+    /// Encodes and writes all of `buf` at `offset`, with the bounds semantics
+    /// of `FileRegion::write()`. Blocks only partly covered by `buf` are read
+    /// and decoded first; any part of them past the end of the file is
+    /// treated as zeros.
+    pub fn write(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        subrange(&self.region.range, offset..end)?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let block_size = self.codec.block_size() as u64;
+        let first = offset / block_size;
+        let start = first * block_size;
+        let span = (round_up(end, block_size) - start) as usize;
+        let mut blocks = if offset.is_multiple_of(block_size) && end.is_multiple_of(block_size) {
+            Vec::with_capacity(span)
+        } else {
+            self.read_blocks(offset, end)?.1
+        };
+        let existing = blocks.len() - blocks.len() % block_size as usize;
+        blocks.truncate(existing);
+        self.decode(first, &mut blocks);
+        blocks.resize(span, 0);
+        let skip = (offset - start) as usize;
+        blocks[skip..skip + buf.len()].copy_from_slice(buf);
+        for (i, block) in blocks.chunks_mut(block_size as usize).enumerate() {
+            self.codec.encode(first + i as u64, block);
+        }
+        self.region.write_full(start, &blocks)?;
+        Ok(buf.len())
+    }
+
+    /// Reads the stored blocks covering `offset..end`. Returns the index of
+    /// the first block and the bytes read, which may stop short at the end
+    /// of the file.
+    fn read_blocks(&self, offset: u64, end: u64) -> Result<(u64, Vec<u8>), FileRegionError> {
+        let block_size = self.codec.block_size() as u64;
+        let first = offset / block_size;
+        let start = first * block_size;
+        let len = usize::try_from(round_up(end, block_size) - start)
+            .map_err(|_| RegionError::EndOverflow)?;
+        let mut blocks = vec![0; len];
+        let n = self.region.read_full(start, &mut blocks)?;
+        blocks.truncate(n);
+        Ok((first, blocks))
+    }
+
+    fn decode(&self, first: u64, blocks: &mut [u8]) {
+        for (i, block) in blocks.chunks_mut(self.codec.block_size()).enumerate() {
+            self.codec.decode(first + i as u64, block);
+        }
+    }
+}
+
+fn round_up(n: u64, multiple: u64) -> u64 {
+    n.div_ceil(multiple) * multiple
+}
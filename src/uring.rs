@@ -0,0 +1,196 @@
+//! Batched region I/O through `io_uring` (Linux only, `uring` feature).
+//!
+//! Queue many bounded reads and writes, possibly across several regions and
+//! files, then submit them together with far fewer system calls than one
+//! `read()`/`write_at()` call each.
+
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::os::fd::{AsRawFd, RawFd};
+
+use io_uring::{opcode, types, EnterFlags, IoUring};
+
+use super::core::{clamped_subrange, subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+enum Op<'r> {
+    Read {
+        fd: RawFd,
+        buf: &'r mut [u8],
+        offset: u64,
+    },
+    Write {
+        fd: RawFd,
+        buf: &'r [u8],
+        offset: u64,
+    },
+}
+
+/// A batch of positional reads and writes, bounds-checked against their
+/// regions when queued and executed by `submit()`.
+///
+/// The batch borrows the buffers and the regions' files for `'r`, so both
+/// stay alive and untouched until the kernel has completed every operation.
+pub struct UringBatch<'r> {
+    ring: IoUring,
+    ops: Vec<Op<'r>>,
+}
+
+impl<'r> UringBatch<'r> {
+    /// Creates an empty batch backed by a new ring with `entries` submission
+    /// slots. Batches larger than the ring are submitted in several rounds.
+    ///
+    /// Returns an I/O error if the kernel does not support (or permits)
+    /// `io_uring`.
+    pub fn new(entries: u32) -> IoResult<UringBatch<'r>> {
+        Ok(UringBatch {
+            ring: IoUring::new(entries)?,
+            ops: Vec::new(),
+        })
+    }
+
+    /// Returns the number of queued operations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns true if no operations are queued.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Queues a read at `offset` into `buf`. Has the same bounds semantics as
+    /// `FileRegion::read()`: the read is limited to the rest of the region,
+    /// and starting beyond the region is an error.
+    pub fn read_at(
+        &mut self,
+        region: &FileRegion<'r>,
+        offset: u64,
+        buf: &'r mut [u8],
+    ) -> Result<(), RegionError> {
+        let len = buf.len().min(u32::MAX as usize);
+        let range = clamped_subrange(&region.range, offset, len)?;
+        self.ops.push(Op::Read {
+            fd: region.file.as_raw_fd(),
+            buf: &mut buf[..(range.end - range.start) as usize],
+            offset: range.start,
+        });
+        Ok(())
+    }
+
+    /// Queues a write of `buf` at `offset`. Has the same bounds semantics as
+    /// `FileRegion::write()`: the whole buffer must fit in the region.
+    pub fn write_at(
+        &mut self,
+        region: &FileRegion<'r>,
+        offset: u64,
+        buf: &'r [u8],
+    ) -> Result<(), RegionError> {
+        let end = offset
+            .checked_add(buf.len() as u64)
+            .ok_or(RegionError::EndOverflow)?;
+        let range = subrange(&region.range, offset..end)?;
+        if buf.len() > u32::MAX as usize {
+            return Err(RegionError::EndOverflow);
+        }
+        self.ops.push(Op::Write {
+            fd: region.file.as_raw_fd(),
+            buf,
+            offset: range.start,
+        });
+        Ok(())
+    }
+
+    /// Submits every queued operation and waits for all of them to complete.
+    /// Returns one result per operation, in the order they were queued: the
+    /// number of bytes transferred, which (as with `read()`/`write_at()`)
+    /// may be short, or the operation's I/O error.
+    ///
+    /// Returns an outer error only if the ring itself fails. Interrupted
+    /// waits are retried; after any other failure, waits for the operations
+    /// already handed to the kernel to complete before returning, so none can
+    /// touch a buffer once the borrow ends.
+    pub fn submit(mut self) -> Result<Vec<IoResult<usize>>, FileRegionError> {
+        let mut results: Vec<Option<IoResult<usize>>> = (0..self.ops.len()).map(|_| None).collect();
+        let capacity = self.ring.params().sq_entries() as usize;
+        let mut next = 0;
+        while next < self.ops.len() {
+            let round = (self.ops.len() - next).min(capacity);
+            for index in next..next + round {
+                let entry = match &mut self.ops[index] {
+                    Op::Read { fd, buf, offset } => {
+                        opcode::Read::new(types::Fd(*fd), buf.as_mut_ptr(), buf.len() as u32)
+                            .offset(*offset)
+                            .build()
+                    }
+                    Op::Write { fd, buf, offset } => {
+                        opcode::Write::new(types::Fd(*fd), buf.as_ptr(), buf.len() as u32)
+                            .offset(*offset)
+                            .build()
+                    }
+                }
+                .user_data(index as u64);
+                // SAFETY: the buffer and file descriptor are borrowed for `'r`,
+                // which outlives this call, and this call does not return
+                // until every entry of this round the kernel has accepted has
+                // completed: the loop below waits for all of them, and
+                // `drain()` for the rest if submission fails.
+                unsafe { self.ring.submission().push(&entry) }
+                    .expect("round does not exceed the ring capacity");
+            }
+            let mut completed = 0;
+            while completed < round {
+                match self.ring.submit_and_wait(round - completed) {
+                    Ok(_) => {}
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                    Err(error) => {
+                        self.drain(round - completed);
+                        return Err(error.into());
+                    }
+                }
+                for cqe in self.ring.completion() {
+                    results[cqe.user_data() as usize] = Some(match cqe.result() {
+                        n if n >= 0 => Ok(n as usize),
+                        errno => Err(IoError::from_raw_os_error(-errno)),
+                    });
+                    completed += 1;
+                }
+            }
+            next += round;
+        }
+        Ok(results
+            .into_iter()
+            .map(|result| result.expect("every operation completed"))
+            .collect())
+    }
+
+    /// Waits, after a failed submission, until every operation of the current
+    /// round that reached the kernel has completed. `pending` counts the
+    /// round's operations not yet completed; those still in the submission
+    /// queue never reach the kernel, as the ring is dropped unsubmitted.
+    fn drain(&mut self, pending: usize) {
+        let mut in_flight = pending - self.ring.submission().len();
+        loop {
+            in_flight -= self.ring.completion().count();
+            if in_flight == 0 {
+                return;
+            }
+            // SAFETY: only waits for completions; submits nothing and passes
+            // no signal mask.
+            let result = unsafe {
+                self.ring.submitter().enter::<libc::sigset_t>(
+                    0,
+                    in_flight as u32,
+                    EnterFlags::GETEVENTS.bits(),
+                    None,
+                )
+            };
+            if let Err(error) = result {
+                if error.kind() != ErrorKind::Interrupted {
+                    // Returning now would let the kernel write into buffers
+                    // the caller may reuse or free.
+                    std::process::abort();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::core::{validate_range, FileRegion};
+use super::error::{FileRegionError, RegionError};
+
+/// A region paired with a cached length of its file, so that validation in a
+/// hot loop checks against the cached value instead of calling `metadata()`
+/// each time.
+///
+/// The cache is never updated implicitly. If the file may be truncated or
+/// extended through another handle or by another process, call
+/// `refresh_len()` when such a change is possible.
+pub struct ValidatedRegion<'a> {
+    region: FileRegion<'a>,
+    file_len: u64,
+}
+
+impl<'a> FileRegion<'a> {
+    /// Returns a `ValidatedRegion` that validates against `len` as the
+    /// file's length, without calling `metadata()`. Performs no I/O.
+    ///
+    /// Returns a `RegionError` if the region is not within `len` bytes.
+    pub fn with_known_len(self, len: u64) -> Result<ValidatedRegion<'a>, RegionError> {
+        validate_range(&self.range, len)?;
+        Ok(ValidatedRegion {
+            region: self,
+            file_len: len,
+        })
+    }
+}
+
+impl<'a> ValidatedRegion<'a> {
+    /// Creates a new `ValidatedRegion`, reading the file's length once and
+    /// validating the region against it.
+    pub fn new(region: FileRegion<'a>) -> Result<ValidatedRegion<'a>, FileRegionError> {
+        let len = region.file.metadata()?.len();
+        Ok(region.with_known_len(len)?)
+    }
+
+    /// Returns the region.
+    pub fn region(&self) -> &FileRegion<'a> {
+        &self.region
+    }
+
+    /// Consumes the `ValidatedRegion`, returning the region.
+    pub fn into_inner(self) -> FileRegion<'a> {
+        self.region
+    }
+
+    /// Returns the cached length of the file. Performs no I/O.
+    pub fn known_len(&self) -> u64 {
+        self.file_len
+    }
+
+    /// Reads the file's current length into the cache and returns it. Does
+    /// not validate; call `validate()` afterwards to check the region against
+    /// the new length.
+    pub fn refresh_len(&mut self) -> Result<u64, FileRegionError> {
+        self.file_len = self.region.file.metadata()?.len();
+        Ok(self.file_len)
+    }
+
+    /// Returns true if the region is within the cached file length. Performs
+    /// no I/O.
+    pub fn is_valid(&self) -> bool {
+        validate_range(&self.region.range, self.file_len).is_ok()
+    }
+
+    /// Validates the region against the cached file length, with the same
+    /// errors as `FileRegion::validate()`. Performs no I/O.
+    pub fn validate(&self) -> Result<(), RegionError> {
+        validate_range(&self.region.range, self.file_len)
+    }
+
+    /// Like `FileRegion::checked_read()`, but validates against the cached
+    /// file length instead of calling `metadata()`.
+    pub fn checked_read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        self.validate()?;
+        self.region.read(offset, buf)
+    }
+
+    /// Like `FileRegion::checked_write()`, but validates against the cached
+    /// file length instead of calling `metadata()`.
+    pub fn checked_write(&self, offset: u64, buf: &[u8]) -> Result<usize, FileRegionError> {
+        self.validate()?;
+        self.region.write(offset, buf)
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::{Error as IoError, ErrorKind};
+
+use super::backend::{RegionBackend, WriteBackend};
+use super::core::FileRegion;
+use super::error::{FileRegionError, RegionError, ShortReadError};
+
+/// The longest LEB128 encoding of a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+impl<B: RegionBackend + ?Sized> FileRegion<'_, B> {
+    /// Reads an unsigned LEB128 varint at `offset`, returning the value and
+    /// the number of bytes it occupied. Reads at most 10 bytes in one call.
+    ///
+    /// Returns `RegionError::EndOutOfBounds` if the varint runs past the end
+    /// of the region, `FileRegionError::ShortRead` if it runs past the end of
+    /// the file, or an I/O error of kind `InvalidData` if it is longer than
+    /// 10 bytes or overflows a `u64`.
+    pub fn read_varint(&self, offset: u64) -> Result<(u64, usize), FileRegionError> {
+        let available = self.len().checked_sub(offset).filter(|&n| n > 0).ok_or(
+            RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.len(),
+            },
+        )?;
+        let mut buf = [0; MAX_VARINT_LEN];
+        let want = MAX_VARINT_LEN.min(usize::try_from(available).unwrap_or(usize::MAX));
+        let read = self.read_full(offset, &mut buf[..want])?;
+        let mut value = 0u64;
+        for (i, &byte) in buf[..read].iter().enumerate() {
+            let bits = u64::from(byte & 0x7f);
+            if i == MAX_VARINT_LEN - 1 && byte > 1 {
+                return Err(invalid_varint());
+            }
+            value |= bits << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok((value, i + 1));
+            }
+        }
+        if read == MAX_VARINT_LEN {
+            Err(invalid_varint())
+        } else if read == want {
+            Err(FileRegionError::Region(RegionError::EndOutOfBounds {
+                end: self.len() + 1,
+                limit: self.len(),
+            }))
+        } else {
+            Err(FileRegionError::ShortRead(ShortReadError {
+                requested: read + 1,
+                read,
+            }))
+        }
+    }
+
+    /// Reads a signed varint at `offset`, stored zigzag-encoded so that small
+    /// negative numbers stay short. See `read_varint()`.
+    pub fn read_varint_signed(&self, offset: u64) -> Result<(i64, usize), FileRegionError> {
+        let (value, len) = self.read_varint(offset)?;
+        Ok(((value >> 1) as i64 ^ -((value & 1) as i64), len))
+    }
+}
+
+impl<B: WriteBackend + ?Sized> FileRegion<'_, B> {
+    /// Writes `value` as an unsigned LEB128 varint at `offset`, returning the
+    /// number of bytes written (1 to 10). Has the same bounds semantics as
+    /// `write()`.
+    pub fn write_varint(&self, offset: u64, mut value: u64) -> Result<usize, FileRegionError> {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        self.write_full(offset, &buf[..len])?;
+        Ok(len)
+    }
+
+    /// Writes `value` as a zigzag-encoded signed varint at `offset`. See
+    /// `write_varint()`.
+    pub fn write_varint_signed(&self, offset: u64, value: i64) -> Result<usize, FileRegionError> {
+        self.write_varint(offset, ((value << 1) ^ (value >> 63)) as u64)
+    }
+}
+
+fn invalid_varint() -> FileRegionError {
+    FileRegionError::Io(IoError::new(ErrorKind::InvalidData, "invalid varint"))
+}
@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::{IoSlice, IoSliceMut, Result as IoResult};
+
+use super::core::{clamped_subrange, subrange, FileRegion};
+use super::error::{FileRegionError, RegionError};
+use super::stats;
+
+impl FileRegion<'_> {
+    /// Reads into `bufs` in order, starting at `offset`, reading no further
+    /// than the end of the region. Returns the total number of bytes read,
+    /// which may be short, as with `read()`. Uses a single `preadv` call on
+    /// Linux and Android; elsewhere, reads each buffer in turn, stopping at
+    /// the first short read.
+    ///
+    /// Returns `RegionError::StartOutOfBounds` if `offset` is at or past the
+    /// end of the region.
+    pub fn read_vectored_at(
+        &self,
+        offset: u64,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Result<usize, FileRegionError> {
+        let range = clamped_subrange(&self.range, offset, usize::MAX)?;
+        let start = range.start;
+        let mut limit = (range.end - range.start) as usize;
+        let mut bounded = Vec::with_capacity(bufs.len());
+        for buf in bufs.iter_mut() {
+            if limit == 0 {
+                break;
+            }
+            let n = buf.len().min(limit);
+            bounded.push(IoSliceMut::new(&mut buf[..n]));
+            limit -= n;
+        }
+        stats::record_read(self.stats.as_deref(), || {
+            preadv(self.file, &mut bounded, start)
+        })
+        .map_err(FileRegionError::Io)
+    }
+
+    /// Writes `bufs` in order, starting at `offset`. Returns the total number
+    /// of bytes written, which may be short, as with `write_at()`. Uses a
+    /// single `pwritev` call on Linux and Android; elsewhere, writes each
+    /// buffer in turn, stopping at the first short write.
+    ///
+    /// Has the same bounds semantics as `write()`: if all of `bufs` does not
+    /// fit in the region, writes nothing and returns a region error.
+    pub fn write_vectored_at(
+        &self,
+        offset: u64,
+        bufs: &[IoSlice<'_>],
+    ) -> Result<usize, FileRegionError> {
+        let total = bufs
+            .iter()
+            .try_fold(0u64, |total, buf| total.checked_add(buf.len() as u64))
+            .ok_or(RegionError::EndOverflow)?;
+        let end = offset.checked_add(total).ok_or(RegionError::EndOverflow)?;
+        let range = subrange(&self.range, offset..end)?;
+        stats::record_write(self.stats.as_deref(), || {
+            pwritev(self.file, bufs, range.start)
+        })
+        .map_err(FileRegionError::Io)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn preadv(file: &File, bufs: &mut [IoSliceMut<'_>], offset: u64) -> IoResult<usize> {
+    use std::os::fd::AsRawFd;
+
+    let offset = libc::off_t::try_from(offset).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+    let count = libc::c_int::try_from(bufs.len().min(libc::UIO_MAXIOV as usize)).unwrap_or(0);
+    loop {
+        // SAFETY: `IoSliceMut` is ABI-compatible with `iovec` on Unix, and the
+        // descriptor is borrowed from a live `File`.
+        let n = unsafe {
+            libc::preadv(
+                file.as_raw_fd(),
+                bufs.as_ptr().cast::<libc::iovec>(),
+                count,
+                offset,
+            )
+        };
+        if n >= 0 {
+            return Ok(n as usize);
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn pwritev(file: &File, bufs: &[IoSlice<'_>], offset: u64) -> IoResult<usize> {
+    use std::os::fd::AsRawFd;
+
+    let offset = libc::off_t::try_from(offset).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+    let count = libc::c_int::try_from(bufs.len().min(libc::UIO_MAXIOV as usize)).unwrap_or(0);
+    loop {
+        // SAFETY: `IoSlice` is ABI-compatible with `iovec` on Unix, and the
+        // descriptor is borrowed from a live `File`.
+        let n = unsafe {
+            libc::pwritev(
+                file.as_raw_fd(),
+                bufs.as_ptr().cast::<libc::iovec>(),
+                count,
+                offset,
+            )
+        };
+        if n >= 0 {
+            return Ok(n as usize);
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn preadv(file: &File, bufs: &mut [IoSliceMut<'_>], offset: u64) -> IoResult<usize> {
+    let mut total = 0;
+    for buf in bufs {
+        let n = super::positional::read_at(file, buf, offset + total as u64)?;
+        total += n;
+        if n < buf.len() {
+            break;
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn pwritev(file: &File, bufs: &[IoSlice<'_>], offset: u64) -> IoResult<usize> {
+    let mut total = 0;
+    for buf in bufs {
+        let n = super::positional::write_at(file, buf, offset + total as u64)?;
+        total += n;
+        if n < buf.len() {
+            break;
+        }
+    }
+    Ok(total)
+}
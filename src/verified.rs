@@ -0,0 +1,169 @@
+use std::io::{Result as IoResult, Write};
+
+use super::core::FileRegion;
+use super::error::{FileRegionError, IntegrityError, RegionError, ShortReadError};
+
+/// Length of the stored checksum: a little-endian CRC-32.
+const CHECKSUM_LEN: u64 = 4;
+
+/// A data region paired with a 4-byte checksum region holding the CRC-32
+/// (IEEE) of the data, stored little-endian. Reads verify the data against
+/// the stored checksum and writes update it, so corruption is reported as
+/// `FileRegionError::Integrity` instead of being returned as data.
+///
+/// The checksum may live anywhere: in a header, a trailer, or a sidecar
+/// file. Updating it is not atomic with the data write; a crash between the
+/// two leaves a mismatch that the next read reports.
+pub struct VerifiedRegion<'a> {
+    data: FileRegion<'a>,
+    checksum: FileRegion<'a>,
+}
+
+impl<'a> VerifiedRegion<'a> {
+    /// Creates a new `VerifiedRegion`. Performs no I/O; use `seal()` to
+    /// initialize the checksum of existing data.
+    ///
+    /// Returns `RegionError::LengthMismatch` if `checksum` is not exactly 4
+    /// bytes long.
+    pub fn new(
+        data: FileRegion<'a>,
+        checksum: FileRegion<'a>,
+    ) -> Result<VerifiedRegion<'a>, FileRegionError> {
+        if checksum.len() != CHECKSUM_LEN {
+            return Err(FileRegionError::Region(RegionError::LengthMismatch));
+        }
+        Ok(VerifiedRegion { data, checksum })
+    }
+
+    /// Returns the data region.
+    pub fn data(&self) -> &FileRegion<'a> {
+        &self.data
+    }
+
+    /// Returns the checksum region.
+    pub fn checksum(&self) -> &FileRegion<'a> {
+        &self.checksum
+    }
+
+    /// Computes the checksum of the data and stores it.
+    pub fn seal(&mut self) -> Result<(), FileRegionError> {
+        let actual = self.compute()?;
+        self.checksum.write_array(0, &actual.to_le_bytes())
+    }
+
+    /// Checks the data against the stored checksum without keeping it in
+    /// memory. Returns `FileRegionError::Integrity` on a mismatch.
+    pub fn verify(&self) -> Result<(), FileRegionError> {
+        let actual = self.compute()?;
+        check(self.stored()?, actual)
+    }
+
+    /// Reads and verifies the whole data region. Returns
+    /// `FileRegionError::Integrity` on a mismatch, or
+    /// `FileRegionError::ShortRead` if the file ends before the data region
+    /// does.
+    pub fn read_to_vec(&self) -> Result<Vec<u8>, FileRegionError> {
+        let len = usize::try_from(self.data.len()).map_err(|_| RegionError::EndOverflow)?;
+        let mut buf = vec![0; len];
+        self.data.read_exact(0, &mut buf)?;
+        check(self.stored()?, crc32(!0, &buf) ^ !0)?;
+        Ok(buf)
+    }
+
+    /// Reads into `buf` from `offset` after verifying the whole data region,
+    /// with the bounds semantics of `FileRegion::read()`. Returns the
+    /// number of bytes read.
+    ///
+    /// Reads the entire data region to check it, so it suits small records
+    /// better than large extents.
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FileRegionError> {
+        if offset >= self.data.len() {
+            return Err(FileRegionError::Region(RegionError::StartOutOfBounds {
+                start: offset,
+                limit: self.data.len(),
+            }));
+        }
+        let data = self.read_to_vec()?;
+        let rest = &data[offset as usize..];
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        Ok(n)
+    }
+
+    /// Writes all of `buf` at `offset` with the bounds semantics of
+    /// `FileRegion::write()`, then updates the stored checksum.
+    pub fn write_all(&mut self, offset: u64, buf: &[u8]) -> Result<(), FileRegionError> {
+        self.data.write_full(offset, buf)?;
+        self.seal()
+    }
+
+    fn stored(&self) -> Result<u32, FileRegionError> {
+        Ok(u32::from_le_bytes(self.checksum.read_array(0)?))
+    }
+
+    fn compute(&self) -> Result<u32, FileRegionError> {
+        let mut hasher = Crc32Writer(!0);
+        let read = self.data.copy_to_writer(&mut hasher)?;
+        if read < self.data.len() {
+            return Err(FileRegionError::ShortRead(ShortReadError {
+                requested: usize::try_from(self.data.len()).unwrap_or(usize::MAX),
+                read: read as usize,
+            }));
+        }
+        Ok(hasher.0 ^ !0)
+    }
+}
+
+fn check(expected: u32, actual: u32) -> Result<(), FileRegionError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(FileRegionError::Integrity(IntegrityError {
+            expected,
+            actual,
+        }))
+    }
+}
+
+/// Accumulates a CRC-32 over everything written to it.
+struct Crc32Writer(u32);
+
+impl Write for Crc32Writer {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0 = crc32(self.0, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Updates `crc` (pre-inverted) with `bytes` using the reflected IEEE
+/// polynomial.
+pub(crate) fn crc32(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io::Error as IoError;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use notify::event::{MetadataKind, ModifyKind};
+use notify::{Config, Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::core::FileRegion;
+use super::error::FileRegionError;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A change to the file under a region that may affect the region's bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegionEvent {
+    /// The file was written without its length crossing the end of the
+    /// region. Change notifications do not say which bytes were written, so
+    /// the write may or may not have touched the region.
+    Modified,
+    /// The file was truncated to `len` bytes, below the end of the region.
+    Truncated { len: u64 },
+    /// The file grew to `len` bytes from a length below the end of the
+    /// region, so bytes were added inside the region.
+    Extended { len: u64 },
+    /// The file was removed or renamed away from the watched path. No further
+    /// events will be delivered.
+    Removed,
+}
+
+/// Delivers `RegionEvent`s for changes to the file under a region, using the
+/// platform's change notifications (inotify, FSEvents, kqueue, or
+/// `ReadDirectoryChangesW`) or, failing that, polling.
+///
+/// Only changes that cannot affect the region are filtered out: accesses,
+/// creation, and metadata-only changes such as permissions. Every write is
+/// reported, as `Truncated` or `Extended` if the file's length crossed the end
+/// of the region and as `Modified` otherwise (including appends and
+/// truncation past the region, since the same notification may also cover
+/// writes inside it). A cache may therefore see events for writes elsewhere
+/// in the file, but never misses one inside it.
+pub struct RegionWatcher {
+    file: File,
+    range: Range<u64>,
+    len: u64,
+    events: Receiver<notify::Result<Event>>,
+    _watcher: Box<dyn Watcher + Send>,
+}
+
+impl FileRegion<'_> {
+    /// Watches `path`, which must name the file this region is over, for
+    /// changes that may affect the region. Uses the platform's change
+    /// notifications, falling back to polling every 100 milliseconds if they
+    /// are unavailable.
+    pub fn watch(&self, path: impl AsRef<Path>) -> Result<RegionWatcher, FileRegionError> {
+        let path = path.as_ref();
+        let (tx, events) = mpsc::channel();
+        let native = RecommendedWatcher::new(tx, Config::default()).and_then(|mut watcher| {
+            watcher.watch(path, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        match native {
+            Ok(watcher) => RegionWatcher::new(self, events, Box::new(watcher)),
+            Err(_) => self.watch_polling(path, DEFAULT_POLL_INTERVAL),
+        }
+    }
+
+    /// Watches `path`, which must name the file this region is over, by
+    /// polling its metadata every `interval`. Works on file systems that do
+    /// not support change notifications, such as many network file systems.
+    pub fn watch_polling(
+        &self,
+        path: impl AsRef<Path>,
+        interval: Duration,
+    ) -> Result<RegionWatcher, FileRegionError> {
+        let (tx, events) = mpsc::channel();
+        let config = Config::default().with_poll_interval(interval);
+        let mut watcher = PollWatcher::new(tx, config).map_err(notify_error)?;
+        watcher
+            .watch(path.as_ref(), RecursiveMode::NonRecursive)
+            .map_err(notify_error)?;
+        RegionWatcher::new(self, events, Box::new(watcher))
+    }
+}
+
+impl RegionWatcher {
+    fn new(
+        region: &FileRegion<'_>,
+        events: Receiver<notify::Result<Event>>,
+        watcher: Box<dyn Watcher + Send>,
+    ) -> Result<RegionWatcher, FileRegionError> {
+        let file = region.file.try_clone()?;
+        let len = file.metadata()?.len();
+        Ok(RegionWatcher {
+            file,
+            range: region.range.clone(),
+            len,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the range being watched, in absolute file offsets.
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Blocks until a change that may affect the region occurs.
+    pub fn recv(&mut self) -> Result<RegionEvent, FileRegionError> {
+        loop {
+            let event = self.events.recv().map_err(|_| disconnected())?;
+            if let Some(event) = self.filter(event)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Blocks until a change that may affect the region occurs or `timeout`
+    /// elapses. Returns `None` on timeout.
+    pub fn recv_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<RegionEvent>, FileRegionError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let event = match self.events.recv_timeout(remaining) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+            };
+            if let Some(event) = self.filter(event)? {
+                return Ok(Some(event));
+            }
+        }
+    }
+
+    /// Returns the next pending change that may affect the region, if any,
+    /// without blocking.
+    pub fn try_recv(&mut self) -> Result<Option<RegionEvent>, FileRegionError> {
+        while let Ok(event) = self.events.try_recv() {
+            if let Some(event) = self.filter(event)? {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Classifies a raw notification, returning `None` if it cannot affect the
+    /// region. Reads the file length to tell truncation and growth into the
+    /// region apart from other writes.
+    fn filter(
+        &mut self,
+        event: notify::Result<Event>,
+    ) -> Result<Option<RegionEvent>, FileRegionError> {
+        let kind = event.map_err(notify_error)?.kind;
+        let writes = match kind {
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_)) => {
+                return Ok(Some(RegionEvent::Removed));
+            }
+            EventKind::Modify(ModifyKind::Metadata(kind)) => {
+                matches!(kind, MetadataKind::Any | MetadataKind::WriteTime)
+            }
+            EventKind::Modify(_) | EventKind::Any | EventKind::Other => true,
+            EventKind::Access(_) | EventKind::Create(_) => return Ok(None),
+        };
+        let len = self.file.metadata()?.len();
+        let old = std::mem::replace(&mut self.len, len);
+        let event = if len < old && len < self.range.end {
+            Some(RegionEvent::Truncated { len })
+        } else if len > old && old < self.range.end {
+            Some(RegionEvent::Extended { len })
+        } else {
+            (writes || len != old).then_some(RegionEvent::Modified)
+        };
+        Ok(event)
+    }
+}
+
+fn notify_error(error: notify::Error) -> FileRegionError {
+    match error {
+        notify::Error {
+            kind: notify::ErrorKind::Io(error),
+            ..
+        } => FileRegionError::Io(error),
+        error => FileRegionError::Io(IoError::other(error)),
+    }
+}
+
+fn disconnected() -> FileRegionError {
+    FileRegionError::Io(IoError::other("file watcher stopped"))
+}